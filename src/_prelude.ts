@@ -1,49 +1,56 @@
-// Exports common NEAR Rust SDK types based on https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/.
-
 /**
- * Represents an 64 bits unsigned integer encoded as a `string`.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/struct.U64.html.
+ * Raw `u64` integer serialized as a JSON `number`.
+ * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U64`.
  */
-export type U64 = string;
+export type RawU64 = number;
 
 /**
- * Represents an 64 bits signed integer encoded as a `string`.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/struct.I64.html.
+ * Raw `i64` integer serialized as a JSON `number`.
+ * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I64`.
  */
-export type I64 = string;
+export type RawI64 = number;
 
 /**
- * Represents an 128 bits unsigned integer encoded as a `string`.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/struct.U128.html.
+ * Raw `u128` integer serialized as a JSON `number`.
+ * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U128`.
  */
-export type U128 = string;
+export type RawU128 = number;
 
 /**
- * Represents an 128 bits signed integer encoded as a `string`.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/struct.I128.html.
+ * Raw `i128` integer serialized as a JSON `number`.
+ * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I128`.
  */
-export type I128 = string;
+export type RawI128 = number;
 
 /**
- * Represents an encoded array of bytes into a `string`.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/struct.Base64VecU8.html.
+ * Options of a call to a change method.
+ * A deposit can only be attached to payable methods, see `PayableCallOptions`.
  */
-export type Base64VecU8 = string;
+export type CallOptions = {
+    /**
+     * Amount of gas to attach to the call, in gas units.
+     */
+    gas?: string | bigint;
 
-/**
- * Balance is a type for storing amounts of tokens, specified in yoctoNEAR.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.Balance.html.
- */
-export type Balance = U128;
+    /**
+     * Metadata passed to the wallet when the call is signed through it.
+     */
+    walletMeta?: string;
 
-/**
- * Account identifier. This is the human readable UTF8 string which is used internally to index accounts on the network and their respective state.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/struct.AccountId.html.
- */
-export type AccountId = string;
+    /**
+     * URL the wallet redirects to after the call is signed through it.
+     */
+    walletCallbackUrl?: string;
+
+    attachedDeposit?: never;
+};
 
 /**
- * DEPRECATED since 4.0.0.
- * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/type.ValidAccountId.html.
+ * Options of a call to a payable change method.
  */
-export type ValidAccountId = string;
+export type PayableCallOptions = Omit<CallOptions, "attachedDeposit"> & {
+    /**
+     * Amount of NEAR to attach to the call, in yoctoNEAR.
+     */
+    attachedDeposit?: string;
+};
@@ -0,0 +1,276 @@
+//! Functions to emit the NEAR ABI of a contract.
+//!
+//! The ABI is a JSON document describing the contract's methods and types.
+//! See <https://github.com/near/abi> for its specification.
+
+use std::ops::Deref;
+
+use serde_json::{json, Map, Value};
+use syn::{ImplItemMethod, Pat, ReturnType, Type};
+
+use crate::{
+    contract::{Contract, NearItemTrait},
+    error::Result,
+    near_sdk_syn::{is_type, result_types, type_args, NearImpl, NearMethod},
+    schema::{schema_definitions, schema_description, schema_type},
+};
+
+/// Version of the NEAR ABI schema emitted by these functions.
+pub const ABI_SCHEMA_VERSION: &str = "0.4.0";
+
+/// Returns the NEAR ABI of the given `contract`.
+/// The `root_schema` contains the JSON Schema definitions of all types declared in the contract.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.name = Some("Contract".to_string());
+/// let abi = near_syn::abi::abi_root(&contract).unwrap();
+/// assert_eq!(abi, serde_json::json!({
+///     "schema_version": "0.4.0",
+///     "metadata": { "name": "Contract" },
+///     "body": {
+///         "functions": [],
+///         "root_schema": {
+///             "$schema": "http://json-schema.org/draft-07/schema#",
+///             "definitions": {},
+///         },
+///     },
+/// }));
+/// ```
+pub fn abi_root(contract: &Contract) -> Result<Value> {
+    Ok(json!({
+        "schema_version": ABI_SCHEMA_VERSION,
+        "metadata": abi_metadata(contract),
+        "body": {
+            "functions": abi_functions(contract)?,
+            "root_schema": {
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "definitions": schema_definitions(contract)?,
+            },
+        },
+    }))
+}
+
+/// Returns the ABI `metadata` of the given `contract`.
+/// Only the `name` is known from the source files, when the contract has one.
+pub fn abi_metadata(contract: &Contract) -> Value {
+    let mut metadata = Map::new();
+    if let Some(name) = &contract.name {
+        metadata.insert("name".to_string(), json!(name));
+    }
+
+    Value::Object(metadata)
+}
+
+/// Returns the ABI `functions` of the given `contract`.
+/// Init methods come first, followed by view and change methods, and `private` callbacks.
+pub fn abi_functions(contract: &Contract) -> Result<Vec<Value>> {
+    let mut functions = Vec::new();
+    for name in contract
+        .init_methods
+        .iter()
+        .chain(&contract.view_methods)
+        .chain(&contract.change_methods)
+        .chain(contract.private_callbacks())
+    {
+        if let Some((method, item_impl, file)) = contract.methods.get(name) {
+            let item_trait = if let Some(trait_name) = item_impl.get_trait_name() {
+                contract.traits.get(&trait_name)
+            } else {
+                None
+            };
+            let function =
+                abi_function(method, item_trait).map_err(|err| err.in_file(file.as_deref()))?;
+            functions.push(function);
+        }
+    }
+
+    Ok(functions)
+}
+
+/// Returns the ABI function of the given Rust `method`.
+/// Doc-comments from `item_trait`, if any, are included in the `doc` field.
+///
+/// ```
+/// use serde_json::json;
+/// use syn::parse_str;
+/// use near_syn::abi::abi_function;
+///
+/// assert_eq!(abi_function(&parse_str("fn get(&self) -> u32 {}").unwrap(), None).unwrap(), json!({
+///     "name": "get",
+///     "kind": "view",
+///     "result": {
+///         "serialization_type": "json",
+///         "type_schema": { "type": "integer", "format": "uint32", "minimum": 0 },
+///     },
+/// }));
+///
+/// assert_eq!(abi_function(&parse_str("#[payable] fn set(&mut self, x: U128) {}").unwrap(), None).unwrap(), json!({
+///     "name": "set",
+///     "kind": "call",
+///     "modifiers": ["payable"],
+///     "params": {
+///         "serialization_type": "json",
+///         "args": [{ "name": "x", "type_schema": { "type": "string", "pattern": "^[0-9]+$" } }],
+///     },
+/// }));
+/// ```
+///
+/// Borsh-serialized arguments and results are described by the `borsh` serialization type.
+/// Their `type_schema` must be a Borsh schema, which is not derived from the source files,
+/// so it is omitted.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::abi::abi_function;
+/// let method = parse_str("#[result_serializer(borsh)] fn raw(&self) -> Vec<u8> {}").unwrap();
+/// assert_eq!(abi_function(&method, None).unwrap()["result"], json!({ "serialization_type": "borsh" }));
+/// ```
+///
+/// Init methods are `call` functions with the `init` modifier and no result.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::abi::abi_function;
+/// assert_eq!(abi_function(&parse_str("#[init] fn new() -> Self {}").unwrap(), None).unwrap(), json!({
+///     "name": "new",
+///     "kind": "call",
+///     "modifiers": ["init"],
+/// }));
+/// ```
+pub fn abi_function(method: &ImplItemMethod, item_trait: Option<&NearItemTrait>) -> Result<Value> {
+    let mut function = Map::new();
+    function.insert("name".to_string(), json!(method.sig.ident.to_string()));
+    if let Some(doc) = schema_description(&method.join_attrs(item_trait)) {
+        function.insert("doc".to_string(), json!(doc));
+    }
+
+    let kind = if method.is_init() || method.is_mut() {
+        "call"
+    } else {
+        "view"
+    };
+    function.insert("kind".to_string(), json!(kind));
+
+    let mut modifiers = Vec::new();
+    if method.is_init() {
+        modifiers.push("init");
+    }
+    if method.is_payable() {
+        modifiers.push("payable");
+    }
+    if method.is_private() {
+        modifiers.push("private");
+    }
+    if !modifiers.is_empty() {
+        function.insert("modifiers".to_string(), json!(modifiers));
+    }
+
+    let mut args = Vec::new();
+    for pat_type in method.input_args() {
+        if let Pat::Ident(pat_ident) = pat_type.pat.deref() {
+            let mut arg = Map::new();
+            arg.insert("name".to_string(), json!(pat_ident.ident.to_string()));
+            arg.extend(abi_type_schema(&pat_type.ty, method.is_borsh_args())?);
+            args.push(Value::Object(arg));
+        }
+    }
+    if !args.is_empty() {
+        function.insert(
+            "params".to_string(),
+            json!({ "serialization_type": serialization_type(method.is_borsh_args()), "args": args }),
+        );
+    }
+
+    let mut callbacks = Vec::new();
+    for (pat_type, attr_name) in method.callback_args() {
+        let (key, ty) = abi_callback_type(&pat_type.ty, attr_name)?;
+        let callback = json!({ "serialization_type": "json", "type_schema": schema_type(ty)? });
+        if key == "callbacks_vec" {
+            function.insert(key.to_string(), callback);
+        } else {
+            callbacks.push(callback);
+        }
+    }
+    if !callbacks.is_empty() {
+        function.insert("callbacks".to_string(), json!(callbacks));
+    }
+
+    if !method.is_init() {
+        if let Some(ty) = abi_result_type(&method.sig.output)? {
+            let mut result = Map::new();
+            result.insert(
+                "serialization_type".to_string(),
+                json!(serialization_type(method.is_borsh_result())),
+            );
+            result.extend(abi_type_schema(ty, method.is_borsh_result())?);
+            function.insert("result".to_string(), Value::Object(result));
+        }
+    }
+
+    Ok(Value::Object(function))
+}
+
+/// Returns the ABI `serialization_type` of arguments or results, depending on whether
+/// they are `borsh`-serialized, see `NearMethod::is_borsh_args` and `NearMethod::is_borsh_result`.
+fn serialization_type(borsh: bool) -> &'static str {
+    if borsh {
+        "borsh"
+    } else {
+        "json"
+    }
+}
+
+/// Returns the `type_schema` field of an argument or result of type `ty`.
+/// The JSON Schema of `ty` describes JSON-serialized values only,
+/// so there is no field when `borsh`-serialized.
+fn abi_type_schema(ty: &Type, borsh: bool) -> Result<Option<(String, Value)>> {
+    Ok(if borsh {
+        None
+    } else {
+        Some(("type_schema".to_string(), schema_type(ty)?))
+    })
+}
+
+/// Returns the ABI field and the type of the promise result received by a callback parameter
+/// of type `ty` marked with `attr_name`, see `callback_attr`.
+/// `#[callback_result]` parameters receive a `Result<T, PromiseError>`, whereas
+/// `#[callback_vec]` parameters receive a `Vec<T>` of all the promise results.
+fn abi_callback_type<'a>(ty: &'a Type, attr_name: &str) -> Result<(&'static str, &'a Type)> {
+    Ok(match (attr_name, ty) {
+        ("callback_result", _) => ("callbacks", result_types(ty).map_or(ty, |(ok, _)| ok)),
+        ("callback_vec", Type::Path(p)) if is_type(ty, "Vec") => {
+            ("callbacks_vec", type_args(p, 1, "Vec")?[0])
+        }
+        ("callback_vec", _) => ("callbacks_vec", ty),
+        _ => ("callbacks", ty),
+    })
+}
+
+/// Returns the type of the value returned to the caller, if any.
+/// Neither `()` nor `Promise`s return a value to the caller.
+/// `#[handle_result]` methods returning `Result<T, E>` and
+/// methods returning `PromiseOrValue<T>` return `T`.
+fn abi_result_type(ret_type: &ReturnType) -> Result<Option<&Type>> {
+    fn result_type(ty: &Type) -> Result<Option<&Type>> {
+        if let Some((ok, _)) = result_types(ty) {
+            return result_type(ok);
+        }
+
+        match ty {
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(None),
+            ty if is_type(ty, "Promise") => Ok(None),
+            Type::Path(p) if is_type(ty, "PromiseOrValue") => {
+                result_type(type_args(p, 1, "PromiseOrValue")?[0])
+            }
+            ty => Ok(Some(ty)),
+        }
+    }
+
+    match ret_type {
+        ReturnType::Default => Ok(None),
+        ReturnType::Type(_, ty) => result_type(ty),
+    }
+}
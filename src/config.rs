@@ -0,0 +1,419 @@
+//! Project configuration, read from a `near-syn.toml` file,
+//! or from the `[package.metadata.near-syn]` table of a `Cargo.toml` file.
+//!
+//! The configuration describes the inputs of the project and the outputs to generate,
+//! so that running `near-syn` with no arguments regenerates them all.
+//! Paths are relative to the directory of the configuration file.
+//! The `[naming]` table only renames the contract for now,
+//! other generated names, *e.g.*, the `{Contract}Methods` object, follow the contract name.
+//!
+//! ```toml
+//! # Either a crate manifest or a list of Rust source files,
+//! # by default the `Cargo.toml` next to the configuration file.
+//! crate = "Cargo.toml"
+//! files = ["src/lib.rs"]
+//! no-now = true
+//!
+//! [naming]
+//! contract = "NftContract"
+//!
+//! [ts]
+//! output = "web/src/contract.ts"
+//! sdk = 4
+//! zod = true
+//!
+//! [ts.types]
+//! Timestamp = "bigint"
+//!
+//! [md]
+//! readme = "README.md"
+//! sections = ["methods-table", "types"]
+//! start-marker = "<!-- BEGIN {section} -->"
+//! end-marker = "<!-- END {section} -->"
+//!
+//! [abi]
+//! output = "abi.json"
+//! ```
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{
+    de::{self, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{
+    error::{Error, Location, Result},
+    md::MD_SECTION,
+    ts::{SdkVersion, TsOptions},
+};
+
+/// Name of the configuration file looked up by `Config::find`.
+pub const CONFIG_FILE: &str = "near-syn.toml";
+
+/// Configuration of a project.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Cargo manifest of the crate to load, see `Contract::push_crate_root`.
+    pub crate_manifest: Option<PathBuf>,
+
+    /// Rust source files to load, see `Contract::push_file`.
+    pub files: Vec<PathBuf>,
+
+    /// Does not emit date/time information.
+    pub no_now: bool,
+
+    /// Name of the contract in the outputs, see `Contract::rename`.
+    pub contract_name: Option<String>,
+
+    /// TypeScript bindings to generate, if any.
+    pub ts: Option<TsConfig>,
+
+    /// Markdown documentation to generate, if any.
+    pub md: Option<MdConfig>,
+
+    /// File to write the NEAR ABI to, if any.
+    pub abi: Option<PathBuf>,
+
+    /// File to write the JSON Schema to, if any.
+    pub schema: Option<PathBuf>,
+}
+
+/// Configuration of the TypeScript bindings, given by the `[ts]` table.
+#[derive(Debug, Default)]
+pub struct TsConfig {
+    /// File to write the bindings to, or directory when `split` is set.
+    pub output: PathBuf,
+
+    /// Splits the bindings into `types.ts`, `contract.ts` and `index.ts` files.
+    pub split: bool,
+
+    /// Options of the bindings, where `prelude_module` is given by `prelude-from`
+    /// and `types` by the `[ts.types]` table.
+    pub options: TsOptions,
+}
+
+/// Configuration of the Markdown documentation, given by the `[md]` table.
+#[derive(Debug, Default)]
+pub struct MdConfig {
+    /// File to write the whole documentation to, if any.
+    pub output: Option<PathBuf>,
+
+    /// README whose marked sections are regenerated, if any.
+    pub readme: Option<PathBuf>,
+
+    /// Sections of the `readme` to regenerate, all of them when empty.
+    pub sections: Vec<String>,
+
+    /// Template of the line starting a section of the `readme`, `MD_START_MARKER` by default.
+    pub start_marker: Option<String>,
+
+    /// Template of the line ending a section of the `readme`, `MD_END_MARKER` by default.
+    pub end_marker: Option<String>,
+}
+
+impl Config {
+    /// Looks up the configuration of the project in `dir`,
+    /// either in its `near-syn.toml` file or in the `[package.metadata.near-syn]` table of its `Cargo.toml`.
+    /// Returns `None` when there is none.
+    pub fn find<P: AsRef<Path>>(dir: P) -> Result<Option<Self>> {
+        let dir = dir.as_ref();
+        let config_file = dir.join(CONFIG_FILE);
+        if config_file.is_file() {
+            return Self::from_file(config_file).map(Some);
+        }
+
+        let manifest = dir.join("Cargo.toml");
+        if manifest.is_file() {
+            if let Some(file) = Self::read_metadata(&manifest)? {
+                return file.into_config(&manifest).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Reads the configuration in `file`.
+    /// A `Cargo.toml` file must have a `[package.metadata.near-syn]` table.
+    pub fn from_file<P: AsRef<Path>>(file: P) -> Result<Self> {
+        let file = file.as_ref();
+        if file.file_name() == Some("Cargo.toml".as_ref()) {
+            Self::read_metadata(file)?
+                .ok_or_else(|| Error::Config {
+                    message: "missing `[package.metadata.near-syn]` table".to_string(),
+                    file: file.to_path_buf(),
+                })?
+                .into_config(file)
+        } else {
+            Self::parse(&fs::read_to_string(file)?, file)
+        }
+    }
+
+    /// Parses the configuration given by the TOML document `src`,
+    /// whose paths are relative to the directory of `file`.
+    ///
+    /// ```
+    /// use near_syn::config::Config;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = Config::parse(r#"
+    /// files = ["src/lib.rs"]
+    ///
+    /// [ts]
+    /// output = "contract.ts"
+    /// sdk = 5
+    ///
+    /// [ts.types]
+    /// Timestamp = "bigint"
+    /// "#, "project/near-syn.toml").unwrap();
+    /// assert_eq!(config.files, vec![PathBuf::from("project/src/lib.rs")]);
+    /// let ts = config.ts.unwrap();
+    /// assert_eq!(ts.output, PathBuf::from("project/contract.ts"));
+    /// assert_eq!(ts.options.sdk, near_syn::ts::SdkVersion::V5);
+    /// assert_eq!(ts.options.types, vec![("Timestamp".to_string(), "bigint".to_string())]);
+    /// assert!(config.md.is_none());
+    /// ```
+    ///
+    /// Unknown keys are rejected.
+    ///
+    /// ```
+    /// let err = near_syn::config::Config::parse("[ts]\noutptu = \"contract.ts\"", "near-syn.toml").unwrap_err();
+    /// assert!(err.to_string().starts_with("unknown field `outptu`, expected one of `output`, "));
+    /// assert_eq!(err.location().unwrap().line, 2);
+    /// ```
+    pub fn parse<P: AsRef<Path>>(src: &str, file: P) -> Result<Self> {
+        let file = file.as_ref();
+        parse_toml::<ConfigFile>(src, file)?.into_config(file)
+    }
+
+    /// Returns the `[package.metadata.near-syn]` table of the Cargo `manifest`, if any.
+    fn read_metadata(manifest: &Path) -> Result<Option<ConfigFile>> {
+        let src = fs::read_to_string(manifest)?;
+        let manifest: Manifest = parse_toml(&src, manifest)?;
+        Ok(manifest
+            .package
+            .and_then(|package| package.metadata)
+            .and_then(|metadata| metadata.near_syn))
+    }
+}
+
+/// Deserializes the TOML document `src` read from `file`,
+/// reporting both syntax errors and invalid values at the line and column where they were found.
+fn parse_toml<T: for<'de> Deserialize<'de>>(src: &str, file: &Path) -> Result<T> {
+    toml::from_str(src).map_err(|err| {
+        let consumed = &src[..err.span().map_or(0, |span| span.start.min(src.len()))];
+        Error::Syntax {
+            message: err.message().to_string(),
+            location: Location {
+                file: Some(file.to_path_buf()),
+                line: consumed.matches('\n').count() + 1,
+                column: consumed.chars().rev().take_while(|c| *c != '\n').count() + 1,
+            },
+        }
+    })
+}
+
+/// The tables of a `Cargo.toml` file leading to the `[package.metadata.near-syn]` table.
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    metadata: Option<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    #[serde(rename = "near-syn")]
+    near_syn: Option<ConfigFile>,
+}
+
+/// The configuration as written in the file, with paths relative to it.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct ConfigFile {
+    #[serde(rename = "crate")]
+    crate_manifest: Option<PathBuf>,
+    #[serde(default)]
+    files: Vec<PathBuf>,
+    #[serde(default)]
+    no_now: bool,
+    naming: Option<NamingTable>,
+    ts: Option<TsTable>,
+    md: Option<MdTable>,
+    abi: Option<OutputTable>,
+    schema: Option<OutputTable>,
+}
+
+/// The `[naming]` table.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct NamingTable {
+    contract: Option<String>,
+}
+
+/// The `[ts]` table, see `TsConfig`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct TsTable {
+    output: PathBuf,
+    #[serde(default)]
+    split: bool,
+    #[serde(default)]
+    factory: bool,
+    #[serde(default)]
+    actions: bool,
+    #[serde(default)]
+    zod: bool,
+    #[serde(default, deserialize_with = "sdk_version")]
+    sdk: SdkVersion,
+    prelude_from: Option<String>,
+    #[serde(default, deserialize_with = "ordered_strings")]
+    types: Vec<(String, String)>,
+}
+
+/// The `[md]` table, see `MdConfig`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct MdTable {
+    output: Option<PathBuf>,
+    readme: Option<PathBuf>,
+    #[serde(default)]
+    sections: Vec<String>,
+    start_marker: Option<String>,
+    end_marker: Option<String>,
+}
+
+/// The `[abi]` and `[schema]` tables.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct OutputTable {
+    output: PathBuf,
+}
+
+impl ConfigFile {
+    /// Resolves the paths of this configuration relative to the directory of `file`,
+    /// and checks the constraints between its values.
+    fn into_config(self, file: &Path) -> Result<Config> {
+        let dir = file
+            .parent()
+            .filter(|dir| *dir != Path::new("."))
+            .unwrap_or_else(|| Path::new(""));
+        let error = |message: String| Error::Config {
+            message,
+            file: file.to_path_buf(),
+        };
+
+        let mut config = Config {
+            crate_manifest: self.crate_manifest.map(|path| dir.join(path)),
+            files: self.files.into_iter().map(|path| dir.join(path)).collect(),
+            no_now: self.no_now,
+            contract_name: self.naming.and_then(|naming| naming.contract),
+            ts: self.ts.map(|ts| TsConfig {
+                output: dir.join(ts.output),
+                split: ts.split,
+                options: TsOptions {
+                    factory: ts.factory,
+                    actions: ts.actions,
+                    zod: ts.zod,
+                    sdk: ts.sdk,
+                    prelude_module: ts.prelude_from,
+                    types: ts.types,
+                },
+            }),
+            md: None,
+            abi: self.abi.map(|abi| dir.join(abi.output)),
+            schema: self.schema.map(|schema| dir.join(schema.output)),
+        };
+        if config.crate_manifest.is_none() && config.files.is_empty() {
+            let manifest = dir.join("Cargo.toml");
+            if manifest.is_file() {
+                config.crate_manifest = Some(manifest);
+            }
+        }
+
+        if let Some(md) = self.md {
+            if md.output.is_none() && md.readme.is_none() {
+                return Err(error("`md` needs either `output` or `readme`".to_string()));
+            }
+            for (key, marker) in [
+                ("start-marker", &md.start_marker),
+                ("end-marker", &md.end_marker),
+            ] {
+                if matches!(marker, Some(marker) if !marker.contains(MD_SECTION)) {
+                    return Err(error(format!(
+                        "`md.{}` should contain `{}`",
+                        key, MD_SECTION
+                    )));
+                }
+            }
+            config.md = Some(MdConfig {
+                output: md.output.map(|path| dir.join(path)),
+                readme: md.readme.map(|path| dir.join(path)),
+                sections: md.sections,
+                start_marker: md.start_marker,
+                end_marker: md.end_marker,
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+/// Deserializes a near-sdk version given either as a number, *e.g.*, `4`, or as a string, *e.g.*, `"4.x"`.
+fn sdk_version<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<SdkVersion, D::Error> {
+    struct SdkVersionVisitor;
+
+    impl<'de> Visitor<'de> for SdkVersionVisitor {
+        type Value = SdkVersion;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a near-sdk version, either 3, 4 or 5")
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Self::Value, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Self::Value, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(SdkVersionVisitor)
+}
+
+/// Deserializes a table of strings into its entries, keeping the order in which they were written.
+fn ordered_strings<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<(String, String)>, D::Error> {
+    struct OrderedStringsVisitor;
+
+    impl<'de> Visitor<'de> for OrderedStringsVisitor {
+        type Value = Vec<(String, String)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a table of strings")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> std::result::Result<Self::Value, A::Error> {
+            let mut entries = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(OrderedStringsVisitor)
+}
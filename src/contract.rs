@@ -1,12 +1,27 @@
 //! Allows the user to build a NEAR Rust Contract from multiple Rust source files.
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io, mem,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
 use syn::{
-    Attribute, File, ImplItemMethod, Item, ItemEnum, ItemImpl, ItemStruct, ItemTrait, ItemType,
-    TraitItem, TraitItemMethod,
+    fold::{self, Fold},
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    Attribute, Block, File, Generics, Ident, ImplItemMethod, Item, ItemEnum, ItemImpl, ItemMod,
+    ItemStruct, ItemTrait, ItemType, Lit, Meta, TraitItem, TraitItemMethod, Type, TypePath,
+    UseTree, Visibility,
 };
 
-use crate::near_sdk_syn::{NearBindgen, NearImpl, NearMethod, NearSerde};
+use crate::{
+    error::{Error, Location, Result},
+    near_sdk_syn::{
+        unresolved_path, NearBindgen, NearExtContract, NearFunctionError, NearImpl, NearMethod,
+        NearSerde,
+    },
+};
 
 /// Represents a pass to several Rust files to build a NEAR Rust Contract.
 pub struct Contract {
@@ -24,8 +39,9 @@ pub struct Contract {
     /// Keeps track of `impl` items of the contract.
     pub interfaces: Vec<String>,
 
-    ///
-    pub methods: HashMap<String, (ImplItemMethod, ItemImpl)>,
+    /// Exported methods by name, along with the `impl` item declaring them
+    /// and the Rust source file they were found in, if known.
+    pub methods: HashMap<String, (ImplItemMethod, ItemImpl, Option<PathBuf>)>,
 
     /// Keeps track of the `view_methods` in the contract.
     pub init_methods: Vec<String>,
@@ -36,8 +52,56 @@ pub struct Contract {
     /// Keeps track of the `change_methods` in the contract.
     pub change_methods: Vec<String>,
 
+    /// Names of the traits marked as `ext_contract`, in declaration order.
+    /// These traits declare the interface of the external contracts called by this contract.
+    pub ext_contracts: Vec<String>,
+
+    /// Keeps track of the callback methods in the contract,
+    /// *i.e.*, methods receiving the result of a promise.
+    /// Callbacks not marked as `private` are also tracked in either `view_methods` or `change_methods`,
+    /// see `private_callbacks`.
+    pub callbacks: Vec<String>,
+
     ///
-    pub items: Vec<NearItem>,
+    pub items: Vec<ContractItem>,
+
+    /// Types deriving `FunctionError` by name,
+    /// used to document the errors of `#[handle_result]` methods.
+    /// These types are usually not `serde` types, and hence not in `items`.
+    pub errors: HashMap<String, NearItem>,
+
+    /// Names imported by the `use` declarations of each module, along with the path they refer to,
+    /// *e.g.*, `Amount` to `near_sdk::json_types::U128` for `use near_sdk::json_types::U128 as Amount;`.
+    /// Modules are given by their path, see `ContractItem::module`.
+    /// These imports are used to resolve the types of the items pushed into this `Contract`.
+    pub imports: HashMap<Vec<String>, HashMap<String, String>>,
+
+    /// Rust source files read so far, in the order they were read,
+    /// *i.e.*, the files given to `push_file` and the module files reached through `push_crate_root`.
+    pub files: Vec<PathBuf>,
+
+    /// Where the items currently being pushed come from.
+    scope: Scope,
+}
+
+/// A `NearItem` together with the Rust source file and module where it was declared.
+pub struct ContractItem {
+    /// The item declared in the contract.
+    item: NearItem,
+    /// The Rust source file declaring this item, if known.
+    pub file: Option<PathBuf>,
+    /// The path of the module declaring this item, relative to the crate root,
+    /// *e.g.*, `["a", "b"]` for `crate::a::b`.
+    /// Empty when declared at the top level of a pushed file.
+    pub module: Vec<String>,
+}
+
+impl Deref for ContractItem {
+    type Target = NearItem;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
 }
 
 ///
@@ -52,6 +116,18 @@ pub enum NearItem {
     Type(ItemType),
 }
 
+impl NearItem {
+    /// Returns the name of this item, unless it is an `impl`.
+    pub fn ident(&self) -> Option<&Ident> {
+        match self {
+            NearItem::Impl(_) => None,
+            NearItem::Struct(item_struct) => Some(&item_struct.ident),
+            NearItem::Enum(item_enum) => Some(&item_enum.ident),
+            NearItem::Type(item_type) => Some(&item_type.ident),
+        }
+    }
+}
+
 ///
 pub struct NearItemTrait {
     ///
@@ -65,6 +141,29 @@ impl NearItemTrait {
     pub fn get(&self, name: &String) -> Option<&TraitItemMethod> {
         self.methods.get(name)
     }
+
+    /// Returns the methods declared in this trait, in declaration order,
+    /// as if they were implemented by a contract.
+    /// This allows the methods of `ext_contract` traits to be translated as exported methods.
+    pub fn impl_methods(&self) -> Vec<ImplItemMethod> {
+        let mut methods = Vec::new();
+        for item in &self.item_trait.items {
+            if let TraitItem::Method(method) = item {
+                methods.push(ImplItemMethod {
+                    attrs: method.attrs.clone(),
+                    vis: Visibility::Inherited,
+                    defaultness: None,
+                    sig: method.sig.clone(),
+                    block: method.default.clone().unwrap_or(Block {
+                        brace_token: Default::default(),
+                        stmts: Vec::new(),
+                    }),
+                });
+            }
+        }
+
+        methods
+    }
 }
 
 impl Deref for NearItemTrait {
@@ -75,6 +174,24 @@ impl Deref for NearItemTrait {
     }
 }
 
+/// The Rust source file and module of the items being pushed.
+#[derive(Clone, Default)]
+struct Scope {
+    file: Option<PathBuf>,
+    module: Vec<String>,
+    /// Only set when loading a whole crate, otherwise `mod foo;` declarations are skipped.
+    dirs: Option<ModDirs>,
+}
+
+/// Directories where the files of `mod foo;` declarations are looked up.
+#[derive(Clone)]
+struct ModDirs {
+    /// Where `foo.rs` or `foo/mod.rs` are looked up.
+    child: PathBuf,
+    /// What `#[path = "..."]` attributes are relative to.
+    path_base: PathBuf,
+}
+
 impl NearItemTrait {
     fn new(item_trait: ItemTrait) -> Self {
         let mut methods = HashMap::new();
@@ -103,30 +220,273 @@ impl Contract {
             init_methods: Vec::new(),
             view_methods: Vec::new(),
             change_methods: Vec::new(),
+            ext_contracts: Vec::new(),
+            callbacks: Vec::new(),
             items: Vec::new(),
+            errors: HashMap::new(),
+            imports: HashMap::new(),
+            files: Vec::new(),
+            scope: Scope::default(),
         }
     }
 
+    /// Returns the `struct`, `enum` or `type` item named `name`,
+    /// either declared as a `serde` type or as an error type.
+    pub fn find_type(&self, name: &str) -> Option<&NearItem> {
+        self.items
+            .iter()
+            .map(|item| &**item)
+            .find(|item| matches!(item.ident(), Some(ident) if ident == name))
+            .or_else(|| self.errors.get(name))
+    }
+
+    /// Returns the callbacks marked as `private`, in declaration order.
+    /// These callbacks can only be called by the contract itself,
+    /// and thus they are in neither `view_methods` nor `change_methods`.
+    pub fn private_callbacks(&self) -> impl Iterator<Item = &String> {
+        self.callbacks.iter().filter(move |name| {
+            !self.view_methods.contains(name) && !self.change_methods.contains(name)
+        })
+    }
+
+    /// Returns the qualified type paths that cannot be resolved, see `unresolved_path`,
+    /// in the items and method signatures translated from this `Contract`.
+    /// These paths are translated by their last segment,
+    /// so they are meant to be reported as warnings.
     ///
-    pub fn push_asts(&mut self, asts: Vec<File>) {
+    /// ```
+    /// let mut contract = near_syn::contract::Contract::new();
+    /// contract.push_ast(syn::parse_str(r#"
+    ///     #[derive(Serialize)]
+    ///     struct Token { owner: other::Owner, ids: Vec<near_sdk::json_types::U64> }
+    ///
+    ///     #[near_bindgen]
+    ///     impl Contract {
+    ///         pub fn get(&self, key: some_dep::Key) -> Token {
+    ///             let _unused: body::Type = todo!();
+    ///         }
+    ///     }"#).unwrap()).unwrap();
+    /// let paths = contract.unresolved_paths().iter().map(ToString::to_string).collect::<Vec<_>>();
+    /// assert_eq!(paths, [
+    ///     "cannot resolve type path `other::Owner`, consider importing it with `use`",
+    ///     "cannot resolve type path `some_dep::Key`, consider importing it with `use`",
+    /// ]);
+    /// ```
+    pub fn unresolved_paths(&self) -> Vec<Error> {
+        #[derive(Default)]
+        struct UnresolvedPaths(Vec<Error>);
+
+        impl<'ast> Visit<'ast> for UnresolvedPaths {
+            fn visit_type_path(&mut self, p: &'ast TypePath) {
+                visit::visit_type_path(self, p);
+                self.0.extend(unresolved_path(p));
+            }
+        }
+
+        let mut errors = Vec::new();
+        for item in &self.items {
+            let mut paths = UnresolvedPaths::default();
+            match &**item {
+                NearItem::Impl(_) => continue,
+                NearItem::Struct(item_struct) => paths.visit_item_struct(item_struct),
+                NearItem::Enum(item_enum) => paths.visit_item_enum(item_enum),
+                NearItem::Type(item_type) => paths.visit_item_type(item_type),
+            }
+            errors.extend(
+                paths
+                    .0
+                    .into_iter()
+                    .map(|err| err.in_file(item.file.as_deref())),
+            );
+        }
+
+        for name in self
+            .init_methods
+            .iter()
+            .chain(&self.view_methods)
+            .chain(&self.change_methods)
+            .chain(self.private_callbacks())
+        {
+            if let Some((method, _, file)) = self.methods.get(name) {
+                let mut paths = UnresolvedPaths::default();
+                paths.visit_signature(&method.sig);
+                errors.extend(paths.0.into_iter().map(|err| err.in_file(file.as_deref())));
+            }
+        }
+
+        for name in &self.ext_contracts {
+            if let Some(item_trait) = self.traits.get(name) {
+                let mut paths = UnresolvedPaths::default();
+                for method in item_trait.impl_methods() {
+                    paths.visit_signature(&method.sig);
+                }
+                errors.extend(paths.0);
+            }
+        }
+
+        errors
+    }
+
+    /// Renames this `Contract` to `name`, along with the `impl`s of the contract type,
+    /// so that its bindings are emitted as if the contract type were named `name`.
+    /// Nothing is renamed when this `Contract` has no name yet.
+    ///
+    /// ```
+    /// let mut contract = near_syn::contract::Contract::new();
+    /// contract.push_ast(syn::parse_str(r#"
+    ///     #[near_bindgen]
+    ///     impl Contract {
+    ///         pub fn get(&self) -> u32 { 42 }
+    ///     }"#).unwrap()).unwrap();
+    /// contract.rename("NftContract").unwrap();
+    /// assert_eq!(contract.name, Some("NftContract".to_string()));
+    ///
+    /// let mut buf = Vec::new();
+    /// near_syn::ts::ts_items(&mut buf, &contract).unwrap();
+    /// assert!(String::from_utf8_lossy(&buf).contains("export interface NftContract {"));
+    /// ```
+    ///
+    /// The new `name` must be a valid identifier.
+    ///
+    /// ```
+    /// let mut contract = near_syn::contract::Contract::new();
+    /// assert!(contract.rename("nft-contract").is_err());
+    /// ```
+    pub fn rename(&mut self, name: &str) -> Result<()> {
+        let ident: Ident = syn::parse_str(name).map_err(|err| Error::Syntax {
+            message: format!("invalid contract name `{}`: {}", name, err),
+            location: Location::from(err.span()),
+        })?;
+        let old_name = match self.name.replace(name.to_string()) {
+            Some(old_name) => old_name,
+            None => return Ok(()),
+        };
+
+        let rename_impl = |item_impl: &mut ItemImpl| {
+            if item_impl.get_impl_name().as_ref() == Some(&old_name) {
+                *item_impl.self_ty = Type::Path(TypePath {
+                    qself: None,
+                    path: ident.clone().into(),
+                });
+            }
+        };
+        for item in &mut self.items {
+            if let NearItem::Impl(item_impl) = &mut item.item {
+                rename_impl(item_impl);
+            }
+        }
+        for (_, item_impl, _) in self.methods.values_mut() {
+            rename_impl(item_impl);
+        }
+
+        Ok(())
+    }
+
+    /// Builds a `Contract` from the crate whose root file is `root_file`, usually `src/lib.rs`.
+    /// See `push_crate_root`.
+    pub fn from_crate_root<P: AsRef<Path>>(root_file: P) -> Result<Self> {
+        let mut contract = Self::new();
+        contract.push_crate_root(root_file)?;
+        Ok(contract)
+    }
+
+    /// Pushes the crate root file `root_file` together with all the module files it declares.
+    ///
+    /// `mod foo;` declarations are resolved as `rustc` does, *i.e.*,
+    /// to either `foo.rs` or `foo/mod.rs`, unless a `#[path = "..."]` attribute is given.
+    /// Modules marked with `#[cfg(test)]` are skipped.
+    /// Each item records the module path where it was declared.
+    pub fn push_crate_root<P: AsRef<Path>>(&mut self, root_file: P) -> Result<()> {
+        let root_file = root_file.as_ref();
+        let dir = root_file
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .to_path_buf();
+        self.push_scoped_file(
+            root_file,
+            Scope {
+                file: Some(root_file.to_path_buf()),
+                module: Vec::new(),
+                dirs: Some(ModDirs {
+                    child: dir.clone(),
+                    path_base: dir,
+                }),
+            },
+        )
+    }
+
+    /// Reads and parses the Rust source file `file_name` and pushes its items into this `Contract`.
+    /// Errors found in this file are reported with `file_name` as their location.
+    ///
+    /// `mod foo;` declarations are not followed, see `push_crate_root` to load a whole crate.
+    pub fn push_file<P: AsRef<Path>>(&mut self, file_name: P) -> Result<()> {
+        let file_name = file_name.as_ref();
+        self.push_scoped_file(
+            file_name,
+            Scope {
+                file: Some(file_name.to_path_buf()),
+                ..Scope::default()
+            },
+        )
+    }
+
+    fn push_scoped_file(&mut self, file_name: &Path, scope: Scope) -> Result<()> {
+        self.files.push(file_name.to_path_buf());
+        let src = fs::read_to_string(file_name).map_err(|err| {
+            io::Error::new(err.kind(), format!("{}: {}", file_name.display(), err))
+        })?;
+        let ast = syn::parse_file(&src).map_err(|err| Error::Syntax {
+            message: err.to_string(),
+            location: Location::from(err.span()),
+        });
+
+        let outer = mem::replace(&mut self.scope, scope);
+        let result = ast.and_then(|ast| self.push_ast(ast));
+        self.scope = outer;
+
+        result.map_err(|err| err.in_file(Some(file_name)))
+    }
+
+    ///
+    pub fn push_asts(&mut self, asts: Vec<File>) -> Result<()> {
         for ast in asts {
-            self.push_ast(ast);
+            self.push_ast(ast)?;
         }
+
+        Ok(())
     }
 
     ///
-    pub fn push_ast(&mut self, ast: File) {
-        if self.push_items(ast.items) {
+    pub fn push_ast(&mut self, ast: File) -> Result<()> {
+        if self.push_items(ast.items)? {
             self.top_level_attrs = ast.attrs;
         }
+
+        Ok(())
     }
 
     ///
-    pub fn push_items(&mut self, items: Vec<Item>) -> bool {
+    pub fn push_items(&mut self, items: Vec<Item>) -> Result<bool> {
+        let mut resolver = PathResolver::new(&items);
+        if !resolver.imports.is_empty() {
+            self.imports
+                .entry(self.scope.module.clone())
+                .or_default()
+                .extend(
+                    resolver
+                        .imports
+                        .iter()
+                        .map(|(name, path)| (name.clone(), path.join("::"))),
+                );
+        }
         let mut declares_bindgen = false;
         for item in items {
+            let item = match item {
+                Item::Mod(_) => item,
+                item => resolver.fold_item(item),
+            };
             match item {
-                Item::Impl(item_impl) => self.push_impl(item_impl),
+                Item::Impl(item_impl) => self.push_impl(item_impl)?,
                 Item::Struct(item_struct) => {
                     if self.push_struct(item_struct) {
                         declares_bindgen = true;
@@ -135,30 +495,116 @@ impl Contract {
                 Item::Enum(item_enum) => self.push_enum(item_enum),
                 Item::Type(item_type) => self.push_typedef(item_type),
                 Item::Trait(item_trait) => self.push_trait(&item_trait),
-                Item::Mod(item_mod) => {
-                    if let Some((_, mod_items)) = item_mod.content {
-                        self.push_items(mod_items);
-                    }
-                }
+                Item::Mod(item_mod) => self.push_mod(item_mod)?,
                 _ => {}
             }
         }
 
-        declares_bindgen
+        Ok(declares_bindgen)
+    }
+
+    fn push_item(&mut self, item: NearItem) {
+        self.items.push(ContractItem {
+            item,
+            file: self.scope.file.clone(),
+            module: self.scope.module.clone(),
+        });
     }
 
-    fn push_impl(&mut self, item_impl: ItemImpl) {
+    fn push_mod(&mut self, item_mod: ItemMod) -> Result<()> {
+        if is_cfg_test(&item_mod.attrs) {
+            return Ok(());
+        }
+
+        let name = item_mod.ident.to_string();
+        let path = path_attr(&item_mod.attrs);
+        let mut scope = self.scope.clone();
+        scope.module.push(name.clone());
+
+        if let Some((_, items)) = item_mod.content {
+            if let Some(dirs) = &mut scope.dirs {
+                dirs.child.push(path.unwrap_or(name));
+                dirs.path_base = dirs.child.clone();
+            }
+
+            let outer = mem::replace(&mut self.scope, scope);
+            let result = self.push_items(items);
+            self.scope = outer;
+            return result.map(|_| ());
+        }
+
+        let dirs = match &self.scope.dirs {
+            Some(dirs) => dirs,
+            None => return Ok(()),
+        };
+        let (file_name, child) = if let Some(path) = path {
+            let file_name = dirs.path_base.join(path);
+            let child = file_name
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .to_path_buf();
+            (file_name, child)
+        } else {
+            let child = dirs.child.join(&name);
+            let candidates = vec![
+                dirs.child.join(format!("{}.rs", name)),
+                child.join("mod.rs"),
+            ];
+            match candidates.iter().find(|file_name| file_name.is_file()) {
+                Some(file_name) => (file_name.clone(), child),
+                None => {
+                    return Err(Error::ModuleNotFound {
+                        name,
+                        candidates,
+                        location: Location::of(&item_mod.ident),
+                    })
+                }
+            }
+        };
+
+        scope.file = Some(file_name.clone());
+        scope.dirs = Some(ModDirs {
+            path_base: file_name
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .to_path_buf(),
+            child,
+        });
+        self.push_scoped_file(&file_name, scope)
+    }
+
+    fn push_impl(&mut self, item_impl: ItemImpl) -> Result<()> {
+        let callbacks = item_impl.callback_methods();
+        for method in &callbacks {
+            let name = method.sig.ident.to_string();
+            self.methods.insert(
+                name.clone(),
+                (
+                    (*method).clone(),
+                    item_impl.clone(),
+                    self.scope.file.clone(),
+                ),
+            );
+            self.callbacks.push(name);
+        }
+
         if let Some(methods) = item_impl.bindgen_methods() {
             if let Some(trait_name) = item_impl.get_trait_name() {
                 self.interfaces.push(trait_name);
             } else if let Some(impl_name) = item_impl.get_impl_name() {
                 self.name = Some(impl_name);
+            } else {
+                return Err(Error::UnsupportedImpl {
+                    location: Location::of(&item_impl.self_ty),
+                });
             }
 
             for method in methods {
                 let name = method.sig.ident.to_string();
-                self.methods
-                    .insert(name.clone(), (method.clone(), item_impl.clone()));
+                self.methods.insert(
+                    name.clone(),
+                    (method.clone(), item_impl.clone(), self.scope.file.clone()),
+                );
 
                 if method.is_init() {
                     &mut self.init_methods
@@ -170,37 +616,224 @@ impl Contract {
                 .push(name);
             }
 
-            self.items.push(NearItem::Impl(item_impl));
+            self.push_item(NearItem::Impl(item_impl));
+        } else if !callbacks.is_empty() {
+            self.push_item(NearItem::Impl(item_impl));
         }
+
+        Ok(())
     }
 
     fn push_struct(&mut self, item_struct: ItemStruct) -> bool {
+        if item_struct.is_function_error() {
+            self.errors.insert(
+                item_struct.ident.to_string(),
+                NearItem::Struct(item_struct.clone()),
+            );
+        }
+
         if !item_struct.is_serde() {
             return false;
         }
 
         let is_bindgen = item_struct.is_bindgen();
-        self.items.push(NearItem::Struct(item_struct));
+        self.push_item(NearItem::Struct(item_struct));
 
         is_bindgen
     }
 
     fn push_enum(&mut self, item_enum: ItemEnum) {
+        if item_enum.is_function_error() {
+            self.errors.insert(
+                item_enum.ident.to_string(),
+                NearItem::Enum(item_enum.clone()),
+            );
+        }
+
         if !item_enum.is_serde() {
             return;
         }
 
-        self.items.push(NearItem::Enum(item_enum));
+        self.push_item(NearItem::Enum(item_enum));
     }
 
     fn push_typedef(&mut self, item_type: ItemType) {
-        self.items.push(NearItem::Type(item_type));
+        self.push_item(NearItem::Type(item_type));
     }
 
     fn push_trait(&mut self, item_trait: &ItemTrait) {
+        if item_trait.ext_contract_name().is_some() {
+            self.ext_contracts.push(item_trait.ident.to_string());
+        }
         self.traits.insert(
             item_trait.ident.to_string(),
             NearItemTrait::new(item_trait.clone()),
         );
     }
 }
+
+/// Resolves the type paths of the items in a module through the names visible in it,
+/// *i.e.*, its submodules and the names imported by its `use` declarations.
+/// For instance, `types::Token` is resolved to `Token` when `types` is imported by `use crate::types;`,
+/// and `Map<K, V>` is resolved to `HashMap<K, V>` when imported by `use std::collections::HashMap as Map;`.
+/// Items are referred to by name regardless of their module, since bindings are flattened.
+struct PathResolver {
+    /// Names of the submodules declared in the module.
+    modules: HashSet<String>,
+    /// Names imported by `use` declarations, along with the path they refer to.
+    imports: HashMap<String, Vec<String>>,
+    /// Names of the type parameters of the item being folded,
+    /// which shadow the names imported by `use` declarations.
+    type_params: Vec<String>,
+}
+
+impl PathResolver {
+    fn new(items: &[Item]) -> Self {
+        fn push_use(
+            tree: &UseTree,
+            prefix: &mut Vec<String>,
+            imports: &mut HashMap<String, Vec<String>>,
+        ) {
+            match tree {
+                UseTree::Path(path) => {
+                    prefix.push(path.ident.to_string());
+                    push_use(&path.tree, prefix, imports);
+                    prefix.pop();
+                }
+                UseTree::Name(name) if name.ident == "self" => {
+                    if let Some(last) = prefix.last() {
+                        imports.insert(last.clone(), prefix.clone());
+                    }
+                }
+                UseTree::Name(name) => {
+                    let mut path = prefix.clone();
+                    path.push(name.ident.to_string());
+                    imports.insert(name.ident.to_string(), path);
+                }
+                UseTree::Rename(rename) if rename.rename == "_" => {}
+                UseTree::Rename(rename) => {
+                    let mut path = prefix.clone();
+                    if rename.ident != "self" {
+                        path.push(rename.ident.to_string());
+                    }
+                    imports.insert(rename.rename.to_string(), path);
+                }
+                UseTree::Glob(_) => {}
+                UseTree::Group(group) => {
+                    for tree in &group.items {
+                        push_use(tree, prefix, imports);
+                    }
+                }
+            }
+        }
+
+        let mut modules = HashSet::new();
+        let mut imports = HashMap::new();
+        for item in items {
+            match item {
+                Item::Mod(item_mod) => {
+                    modules.insert(item_mod.ident.to_string());
+                }
+                Item::Use(item_use) => push_use(&item_use.tree, &mut Vec::new(), &mut imports),
+                _ => {}
+            }
+        }
+
+        Self {
+            modules,
+            imports,
+            type_params: Vec::new(),
+        }
+    }
+
+    /// Folds an item declaring `generics` with `fold_item`,
+    /// so that its type parameters are not resolved as imports.
+    fn with_generics<T>(
+        &mut self,
+        generics: &Generics,
+        item: T,
+        fold_item: fn(&mut Self, T) -> T,
+    ) -> T {
+        let len = self.type_params.len();
+        self.type_params
+            .extend(generics.type_params().map(|param| param.ident.to_string()));
+        let item = fold_item(self, item);
+        self.type_params.truncate(len);
+        item
+    }
+}
+
+impl Fold for PathResolver {
+    fn fold_item_struct(&mut self, item: ItemStruct) -> ItemStruct {
+        let generics = item.generics.clone();
+        self.with_generics(&generics, item, fold::fold_item_struct)
+    }
+
+    fn fold_item_enum(&mut self, item: ItemEnum) -> ItemEnum {
+        let generics = item.generics.clone();
+        self.with_generics(&generics, item, fold::fold_item_enum)
+    }
+
+    fn fold_item_type(&mut self, item: ItemType) -> ItemType {
+        let generics = item.generics.clone();
+        self.with_generics(&generics, item, fold::fold_item_type)
+    }
+
+    fn fold_item_impl(&mut self, item: ItemImpl) -> ItemImpl {
+        let generics = item.generics.clone();
+        self.with_generics(&generics, item, fold::fold_item_impl)
+    }
+
+    fn fold_type_path(&mut self, type_path: TypePath) -> TypePath {
+        let mut type_path = fold::fold_type_path(self, type_path);
+        let path = &mut type_path.path;
+        if type_path.qself.is_some() || path.leading_colon.is_some() {
+            return type_path;
+        }
+
+        let first = path.segments[0].ident.to_string();
+        if self.type_params.contains(&first) {
+            return type_path;
+        }
+
+        if path.segments.len() > 1 {
+            if self.modules.contains(&first) || self.imports.contains_key(&first) {
+                let last = path.segments.pop().unwrap().into_value();
+                path.segments = Punctuated::new();
+                path.segments.push(last);
+            }
+        } else if let Some(target) = self.imports.get(&first) {
+            let span = path.segments[0].ident.span();
+            let target_path = target.join("::");
+            if target_path.ends_with("serde_json::Value")
+                || target_path.ends_with("serde_json::value::Value")
+            {
+                path.segments[0].ident = Ident::new("Value", span);
+                path.segments
+                    .insert(0, Ident::new("serde_json", span).into());
+            } else if let Some(name) = target.last() {
+                path.segments[0].ident = Ident::new(name, span);
+            }
+        }
+
+        type_path
+    }
+}
+
+/// Whether `attrs` contains `#[cfg(test)]`.
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("cfg") && attr.tokens.to_string() == "(test)")
+}
+
+/// Returns the value of the `#[path = "..."]` attribute in `attrs`, if any.
+fn path_attr(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match attr.parse_meta() {
+        Ok(Meta::NameValue(meta)) if meta.path.is_ident("path") => match meta.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
@@ -0,0 +1,237 @@
+//! Errors found while analyzing Rust source files.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// Represents a position in a Rust source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// The Rust source file, if known.
+    pub file: Option<PathBuf>,
+
+    /// 1-based line number.
+    pub line: usize,
+
+    /// 1-based column number.
+    pub column: usize,
+}
+
+impl Location {
+    /// Returns the location where `node` starts.
+    /// The `file` is unknown at this point, see `Error::in_file`.
+    pub fn of<T: Spanned>(node: &T) -> Self {
+        Self::from(node.span())
+    }
+}
+
+impl From<Span> for Location {
+    fn from(span: Span) -> Self {
+        let start = span.start();
+        Self {
+            file: None,
+            line: start.line,
+            column: start.column + 1,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Represents the errors that can occur when analyzing Rust source files
+/// or emitting their corresponding bindings and docs.
+#[derive(Debug)]
+pub enum Error {
+    /// A Rust source file could not be read or the output could not be written.
+    Io(io::Error),
+
+    /// A Rust source file, or a configuration file, has syntax errors.
+    Syntax {
+        /// The error message reported by the parser.
+        message: String,
+        /// Where the syntax error was found.
+        location: Location,
+    },
+
+    /// A Rust type cannot be translated.
+    UnsupportedType {
+        /// The offending type, as written in the source file.
+        ty: String,
+        /// Where the type was found.
+        location: Location,
+    },
+
+    /// A standard library generic type is used with wrong generic arguments,
+    /// *e.g.*, `Option` or `HashMap<U64>`.
+    /// This can only happen on Rust source files that were **not** type-checked by `rustc`.
+    GenericArgs {
+        /// Describes what is wrong with the generic arguments.
+        message: String,
+        /// Where the generic type was found.
+        location: Location,
+    },
+
+    /// A unit `struct` cannot be translated.
+    UnitStruct {
+        /// The name of the `struct`.
+        name: String,
+        /// Where the `struct` was declared.
+        location: Location,
+    },
+
+    /// A tuple variant of an internally tagged `enum`, which `serde` cannot represent.
+    TupleVariant {
+        /// The name of the variant.
+        name: String,
+        /// Where the variant was declared.
+        location: Location,
+    },
+
+    /// The type of an `impl` item is not a named type, *e.g.*, `impl *const u32`.
+    UnsupportedImpl {
+        /// Where the `impl` was declared.
+        location: Location,
+    },
+
+    /// A qualified type path cannot be resolved to a known type or a type of the crate,
+    /// *e.g.*, `other_crate::Token`.
+    UnresolvedPath {
+        /// The offending path, as written in the source file.
+        path: String,
+        /// Where the path was found.
+        location: Location,
+    },
+
+    /// A configuration file is not valid, *e.g.*, it has unknown keys or values of the wrong type.
+    Config {
+        /// Describes what is wrong with the configuration.
+        message: String,
+        /// The configuration file.
+        file: PathBuf,
+    },
+
+    /// The file of a `mod` declaration could not be found when loading a crate.
+    ModuleNotFound {
+        /// The name of the module.
+        name: String,
+        /// The files where the module was looked up.
+        candidates: Vec<PathBuf>,
+        /// Where the module was declared.
+        location: Location,
+    },
+}
+
+/// Result type for operations that can fail with an `Error`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Returns an `UnsupportedType` error for `ty`.
+    pub fn unsupported_type(ty: &syn::Type) -> Self {
+        Error::UnsupportedType {
+            ty: ty.to_token_stream().to_string(),
+            location: Location::of(ty),
+        }
+    }
+
+    /// Returns the location in the source file where this error was found, if any.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Io(_) | Error::Config { .. } => None,
+            Error::Syntax { location, .. }
+            | Error::UnsupportedType { location, .. }
+            | Error::GenericArgs { location, .. }
+            | Error::UnitStruct { location, .. }
+            | Error::TupleVariant { location, .. }
+            | Error::UnsupportedImpl { location }
+            | Error::UnresolvedPath { location, .. }
+            | Error::ModuleNotFound { location, .. } => Some(location),
+        }
+    }
+
+    /// Sets the source `file` of this error's location, unless it is already set.
+    pub fn in_file(mut self, file: Option<&Path>) -> Self {
+        if let Error::Syntax { location, .. }
+        | Error::UnsupportedType { location, .. }
+        | Error::GenericArgs { location, .. }
+        | Error::UnitStruct { location, .. }
+        | Error::TupleVariant { location, .. }
+        | Error::UnsupportedImpl { location }
+        | Error::UnresolvedPath { location, .. }
+        | Error::ModuleNotFound { location, .. } = &mut self
+        {
+            if location.file.is_none() {
+                location.file = file.map(Path::to_path_buf);
+            }
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Syntax { message, .. } => write!(f, "{}", message),
+            Error::UnsupportedType { ty, .. } => write!(f, "type not supported: `{}`", ty),
+            Error::GenericArgs { message, .. } => write!(f, "{}", message),
+            Error::UnitStruct { name, .. } => write!(f, "unit struct not supported: `{}`", name),
+            Error::TupleVariant { name, .. } => write!(
+                f,
+                "tuple variant not supported in internally tagged enum: `{}`",
+                name
+            ),
+            Error::UnsupportedImpl { .. } => write!(f, "Impl struct name not supported"),
+            Error::UnresolvedPath { path, .. } => write!(
+                f,
+                "cannot resolve type path `{}`, consider importing it with `use`",
+                path
+            ),
+            Error::Config { message, file } => {
+                write!(
+                    f,
+                    "invalid configuration in `{}`: {}",
+                    file.display(),
+                    message
+                )
+            }
+            Error::ModuleNotFound {
+                name, candidates, ..
+            } => {
+                write!(f, "file not found for module `{}`, tried", name)?;
+                for (i, candidate) in candidates.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { " and" };
+                    write!(f, "{} `{}`", sep, candidate.display())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
@@ -2,9 +2,15 @@
 #![deny(warnings)]
 #![warn(missing_docs)]
 
+pub mod abi;
+pub mod config;
 pub mod contract;
+pub mod error;
 pub mod md;
 pub mod near_sdk_syn;
+pub mod schema;
 pub mod ts;
+pub mod zod;
 
+pub use error::{Error, Result};
 pub use near_sdk_syn::*;
@@ -3,34 +3,71 @@
 use chrono::Utc;
 use clap::Parser;
 use near_syn::{
+    abi::abi_root,
+    config::{Config, CONFIG_FILE},
     contract::Contract,
-    md::{md_footer, md_items, md_methods_table, md_prelude},
-    ts::{ts_contract_methods, ts_extend_traits, ts_items, ts_prelude},
+    md::{
+        md_footer, md_items, md_methods_table, md_prelude, md_section, MD_END_MARKER, MD_SECTION,
+        MD_START_MARKER,
+    },
+    schema::schema_root,
+    ts::{
+        ts_contract_actions, ts_contract_factory, ts_contract_methods, ts_ext_contracts,
+        ts_extend_traits, ts_header, ts_impl_items, ts_import_exports, ts_items, ts_prelude,
+        ts_type_items, ts_type_overrides, SdkVersion, TsOptions,
+    },
+    zod::{zod_items, zod_prelude, zod_result_schemas, zod_type_overrides},
+    Error, Result,
 };
+use notify_debouncer_mini::{new_debouncer, notify, notify::RecursiveMode};
+use similar::TextDiff;
 use std::{
-    env,
-    fs::{self, File},
-    io::{self, stdout, Read, Write},
-    path::Path,
+    collections::{HashMap, HashSet},
+    env, fs,
+    io::{self, stdout, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::mpsc,
+    time::Duration,
 };
 
-/// Analyzes Rust source files to generate either TypeScript bindings or Markdown documentation
+/// Analyzes Rust source files to generate either TypeScript bindings, Markdown documentation, the NEAR ABI or JSON Schema.
+/// Without a subcommand, generates all the outputs given by the project configuration,
+/// either `near-syn.toml` or the `[package.metadata.near-syn]` table of `Cargo.toml`
 #[derive(Parser)]
 #[clap(version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
+#[clap(args_conflicts_with_subcommands = true)]
 struct Args {
+    /// Reads the project configuration from the given file,
+    /// otherwise it is looked up in the current directory
+    #[clap(long)]
+    config: Option<String>,
+
+    /// Does not write the outputs of the project configuration, but checks they are up to date
+    #[clap(long)]
+    check: bool,
+
     #[clap(subcommand)]
-    cmd: Cmd,
+    cmd: Option<Cmd>,
 }
 
 #[derive(Parser)]
 enum Cmd {
     /// Emits TypeScript bindings
     #[clap(version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
-    TS(EmitArgs),
+    TS(TSEmitArgs),
 
     /// Emits Markdown documentation
     #[clap(version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
     MD(MDEmitArgs),
+
+    /// Emits the NEAR ABI in JSON format
+    #[clap(version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
+    Abi(EmitArgs),
+
+    /// Emits the JSON Schema of types and method arguments
+    #[clap(version = env!("CARGO_PKG_VERSION"), author = env!("CARGO_PKG_AUTHORS"))]
+    Schema(EmitArgs),
 }
 
 #[derive(Parser)]
@@ -40,17 +77,134 @@ struct EmitArgs {
     #[clap(long)]
     no_now: bool,
 
+    /// Cargo manifest (Cargo.toml) of the crate to analize,
+    /// its src/lib.rs and all the modules it declares are loaded
+    #[clap(long = "crate")]
+    crate_manifest: Option<String>,
+
+    /// Name of the contract in the outputs, instead of the name of the struct marked with `#[near_bindgen]`
+    #[clap(long)]
+    contract_name: Option<String>,
+
+    /// Writes the output to the given file instead of stdout
+    #[clap(short, long)]
+    output: Option<String>,
+
+    /// Does not write the output, but checks the output file (or README) is up to date,
+    /// ignoring date/time information.
+    /// Prints a unified diff and exits with an error when it is not
+    #[clap(long)]
+    check: bool,
+
     /// Rust source files (*.rs) to analize
     #[clap()]
     files: Vec<String>,
 }
 
+#[derive(Parser)]
+struct TSEmitArgs {
+    /// Emits a `connect{Contract}` function returning a typed near-api-js `Contract`
+    #[clap(long)]
+    factory: bool,
+
+    /// Emits a wallet-selector `FunctionCall` action builder for each change method
+    #[clap(long)]
+    actions: bool,
+
+    /// Emits zod schemas to validate types and method results at runtime
+    #[clap(long)]
+    zod: bool,
+
+    /// Version of near-sdk whose types are exported, either 3, 4 or 5
+    #[clap(long, default_value = "4")]
+    sdk: SdkVersion,
+
+    /// Imports the near-sdk types from the given module instead of declaring them,
+    /// e.g., a module emitted with --prelude-only
+    #[clap(long)]
+    prelude_from: Option<String>,
+
+    /// Emits only the near-sdk types, to be shared by several bindings through --prelude-from
+    #[clap(long)]
+    prelude_only: bool,
+
+    /// Splits the bindings into the --output directory:
+    /// `types.ts` with the types, `contract.ts` with the contract interfaces and methods,
+    /// and `index.ts` re-exporting both
+    #[clap(long, conflicts_with = "prelude-only")]
+    split: bool,
+
+    /// Declares the Rust type `RUST` as the TypeScript type `TS` instead of translating it,
+    /// e.g., `--type Timestamp=bigint`. Can be given multiple times
+    #[clap(long = "type", value_name = "RUST=TS", parse(try_from_str = parse_type_override))]
+    types: Vec<(String, String)>,
+
+    /// Keeps watching the Rust source files, regenerating the bindings whenever they change
+    #[clap(long, conflicts_with = "check")]
+    watch: bool,
+
+    #[clap(flatten)]
+    emit_args: EmitArgs,
+}
+
+impl TSEmitArgs {
+    fn options(&self) -> TsOptions {
+        TsOptions {
+            factory: self.factory,
+            actions: self.actions,
+            zod: self.zod,
+            sdk: self.sdk,
+            prelude_module: self.prelude_from.clone(),
+            types: self.types.clone(),
+        }
+    }
+}
+
+/// Parses a type override given by `--type`.
+fn parse_type_override(arg: &str) -> std::result::Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, ts)) if !name.trim().is_empty() && !ts.trim().is_empty() => {
+            Ok((name.trim().to_string(), ts.trim().to_string()))
+        }
+        _ => Err(format!("expected `RUST=TS`, found `{}`", arg)),
+    }
+}
+
+/// Parses a README marker template given by `--start-marker` or `--end-marker`.
+fn parse_marker(arg: &str) -> std::result::Result<String, String> {
+    if arg.contains(MD_SECTION) {
+        Ok(arg.to_string())
+    } else {
+        Err(format!(
+            "expected `{}` in the marker, found `{}`",
+            MD_SECTION, arg
+        ))
+    }
+}
+
 #[derive(Parser)]
 struct MDEmitArgs {
     /// If provided, the output will be embedded in between markers inside the README
     #[clap(long)]
     readme: Option<String>,
 
+    /// Regenerates only the given sections of the README, e.g., `--section types`.
+    /// Can be given multiple times, otherwise all sections are regenerated
+    #[clap(long = "section", requires = "readme")]
+    sections: Vec<String>,
+
+    /// Template of the line starting a section of the README, where `{section}` stands for the section name
+    #[clap(long, default_value = MD_START_MARKER, parse(try_from_str = parse_marker))]
+    start_marker: String,
+
+    /// Template of the line ending a section of the README, where `{section}` stands for the section name
+    #[clap(long, default_value = MD_END_MARKER, parse(try_from_str = parse_marker))]
+    end_marker: String,
+
+    /// Keeps watching the Rust source files, regenerating the documentation whenever they change
+    #[clap(long, conflicts_with = "check")]
+    watch: bool,
+
     #[clap(flatten)]
     emit_args: EmitArgs,
 }
@@ -64,101 +218,644 @@ impl EmitArgs {
         }
     }
 
-    fn contract(&self) -> Contract {
-        let asts = self.files.iter().map(|file| parse_rust(file)).collect();
-        let mut contract = Contract::new();
-        contract.push_asts(asts);
-        contract
+    fn push_files(&self, contract: &mut Contract) -> Result<()> {
+        if let Some(root_file) = self.crate_root() {
+            contract.push_crate_root(root_file)?;
+        }
+        for file in &self.files {
+            contract.push_file(file)?;
+        }
+        if let Some(name) = &self.contract_name {
+            contract.rename(name)?;
+        }
+        Ok(())
+    }
+
+    /// The root file of the crate given by `--crate`, if any.
+    fn crate_root(&self) -> Option<PathBuf> {
+        self.crate_manifest.as_ref().map(|manifest| {
+            Path::new(manifest)
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join("src")
+                .join("lib.rs")
+        })
+    }
+
+    /// The Rust source files given in the command line, including the crate root file.
+    fn input_files(&self) -> Vec<PathBuf> {
+        self.crate_root()
+            .into_iter()
+            .chain(self.files.iter().map(PathBuf::from))
+            .collect()
+    }
+}
+
+impl Cmd {
+    fn emit_args(&self) -> &EmitArgs {
+        match self {
+            Cmd::TS(args) => &args.emit_args,
+            Cmd::MD(args) => &args.emit_args,
+            Cmd::Abi(args) | Cmd::Schema(args) => args,
+        }
+    }
+
+    fn watch(&self) -> bool {
+        match self {
+            Cmd::TS(args) => args.watch,
+            Cmd::MD(args) => args.watch,
+            Cmd::Abi(_) | Cmd::Schema(_) => false,
+        }
+    }
+
+    /// Loads the input files of this command into `contract`.
+    /// Items whose TypeScript type is overridden by `--type` are removed,
+    /// since they are declared by `ts_type_overrides` instead.
+    fn push_files(&self, contract: &mut Contract) -> Result<()> {
+        self.emit_args().push_files(contract)?;
+        if let Cmd::TS(args) = self {
+            contract.items.retain(|item| match item.ident() {
+                Some(ident) => !args.types.iter().any(|(name, _)| ident == name),
+                None => true,
+            });
+        }
+        Ok(())
     }
 }
 
-fn main() -> io::Result<()> {
+/// Returns the commands generating the outputs given by `config`.
+/// When `check` is set, the outputs are checked instead of written.
+fn config_cmds(config: &Config, check: bool) -> Vec<Cmd> {
+    let path = |path: &Path| path.to_string_lossy().into_owned();
+    let emit_args = |output: Option<&Path>| EmitArgs {
+        no_now: config.no_now,
+        crate_manifest: config.crate_manifest.as_deref().map(path),
+        contract_name: config.contract_name.clone(),
+        output: output.map(path),
+        check,
+        files: config.files.iter().map(|file| path(file)).collect(),
+    };
+
+    let mut cmds = Vec::new();
+    if let Some(ts) = &config.ts {
+        cmds.push(Cmd::TS(TSEmitArgs {
+            factory: ts.options.factory,
+            actions: ts.options.actions,
+            zod: ts.options.zod,
+            sdk: ts.options.sdk,
+            prelude_from: ts.options.prelude_module.clone(),
+            prelude_only: false,
+            split: ts.split,
+            types: ts.options.types.clone(),
+            watch: false,
+            emit_args: emit_args(Some(&ts.output)),
+        }));
+    }
+    if let Some(md) = &config.md {
+        if let Some(output) = &md.output {
+            cmds.push(Cmd::MD(MDEmitArgs {
+                readme: None,
+                sections: Vec::new(),
+                start_marker: MD_START_MARKER.to_string(),
+                end_marker: MD_END_MARKER.to_string(),
+                watch: false,
+                emit_args: emit_args(Some(output)),
+            }));
+        }
+        if let Some(readme) = &md.readme {
+            cmds.push(Cmd::MD(MDEmitArgs {
+                readme: Some(path(readme)),
+                sections: md.sections.clone(),
+                start_marker: md
+                    .start_marker
+                    .clone()
+                    .unwrap_or_else(|| MD_START_MARKER.to_string()),
+                end_marker: md
+                    .end_marker
+                    .clone()
+                    .unwrap_or_else(|| MD_END_MARKER.to_string()),
+                watch: false,
+                emit_args: emit_args(None),
+            }));
+        }
+    }
+    if let Some(output) = &config.abi {
+        cmds.push(Cmd::Abi(emit_args(Some(output))));
+    }
+    if let Some(output) = &config.schema {
+        cmds.push(Cmd::Schema(emit_args(Some(output))));
+    }
+
+    cmds
+}
+
+/// Reads the project configuration from `file`, or looks it up in the current directory.
+fn load_config(file: Option<&str>) -> Result<Config> {
+    if let Some(file) = file {
+        return Config::from_file(file);
+    }
+
+    Config::find(".")?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no `{}` file nor `[package.metadata.near-syn]` table in `Cargo.toml` found in the current directory, \
+                see `{} --help` to run a subcommand instead",
+                CONFIG_FILE,
+                env!("CARGO_BIN_NAME")
+            ),
+        )
+        .into()
+    })
+}
+
+fn main() {
     let args = Args::parse();
 
-    match args.cmd {
-        Cmd::TS(args) => emit_ts(&mut stdout(), args).unwrap(),
+    match run(args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            report("error", &err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the given command, or the commands given by the project configuration when there is none.
+/// Returns `false` when checking an output that is out of date, `true` otherwise.
+fn run(args: Args) -> Result<bool> {
+    let cmds = match args.cmd {
+        Some(cmd) if cmd.watch() => {
+            watch(&cmd)?;
+            return Ok(true);
+        }
+        Some(cmd) => vec![cmd],
+        None => config_cmds(&load_config(args.config.as_deref())?, args.check),
+    };
+
+    let mut up_to_date = true;
+    let mut warned = HashSet::new();
+    for cmd in cmds {
+        let mut contract = Contract::new();
+        cmd.push_files(&mut contract)?;
+        // Several commands read the same files, so each warning is reported once.
+        for warning in contract.unresolved_paths() {
+            if warned.insert((
+                warning.to_string(),
+                warning.location().map(ToString::to_string),
+            )) {
+                report("warning", &warning);
+            }
+        }
+        for (path, buf) in emit(&cmd, &contract)? {
+            up_to_date &= write_output(&buf, path.as_deref(), cmd.emit_args().check)?;
+        }
+    }
+
+    Ok(up_to_date)
+}
+
+/// Returns the outputs of the given command for `contract`,
+/// along with the file where each output is written, or `None` for stdout.
+fn emit(cmd: &Cmd, contract: &Contract) -> Result<Vec<(Option<PathBuf>, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let output = match cmd {
+        Cmd::TS(args) if args.split => {
+            let dir = match &args.emit_args.output {
+                Some(dir) => Path::new(dir),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "--split needs the output directory given by --output",
+                    )
+                    .into())
+                }
+            };
+            return Ok(emit_ts_split(args, contract)?
+                .into_iter()
+                .map(|(name, buf)| (Some(dir.join(name)), buf))
+                .collect());
+        }
+        Cmd::TS(args) => {
+            emit_ts(&mut buf, args, contract)?;
+            &args.emit_args.output
+        }
         Cmd::MD(args) => {
-            if let Some(readme) = args.readme {
-                let content = fs::read_to_string(&readme)?;
-                let mut buf = Vec::new();
-                emit_md_table(&mut buf, &args.emit_args, content)?;
-                let mut file = File::create(readme)?;
-                file.write_all(&buf)?;
+            if let Some(readme) = &args.readme {
+                let content = fs::read_to_string(readme)?;
+                emit_md_readme(
+                    &mut buf,
+                    contract,
+                    readme,
+                    content,
+                    &args.sections,
+                    (&args.start_marker, &args.end_marker),
+                )?;
+                &args.readme
             } else {
-                emit_md(&mut stdout(), &args.emit_args)?
+                emit_md(&mut buf, &args.emit_args, contract)?;
+                &args.emit_args.output
             }
         }
+        Cmd::Abi(args) => {
+            emit_abi(&mut buf, contract)?;
+            &args.output
+        }
+        Cmd::Schema(args) => {
+            emit_schema(&mut buf, contract)?;
+            &args.output
+        }
+    };
+
+    Ok(vec![(output.as_ref().map(PathBuf::from), buf)])
+}
+
+/// Watches the Rust source files of the given command, and regenerates its outputs whenever any of them is modified.
+/// These files are the input files together with the module files reachable from the crate root, if any.
+/// Changes are debounced, so that a burst of writes, *e.g.*, when saving many files at once, regenerates the outputs only once.
+/// Outputs are only rewritten when their content, ignoring date/time information, changes.
+/// Errors are reported but do not stop watching.
+fn watch(cmd: &Cmd) -> Result<()> {
+    fn watch_error(err: notify::Error) -> Error {
+        io::Error::other(err).into()
     }
 
-    Ok(())
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(Duration::from_millis(200), tx).map_err(watch_error)?;
+    // Directories are watched instead of files, since editors usually save by replacing files.
+    let mut dirs = HashSet::new();
+    let mut files = HashSet::new();
+    let mut last_outputs = HashMap::new();
+    loop {
+        let mut contract = Contract::new();
+        let result = cmd.push_files(&mut contract).and_then(|_| {
+            for warning in contract.unresolved_paths() {
+                report("warning", &warning);
+            }
+            emit(cmd, &contract)
+        });
+        for file in cmd.emit_args().input_files().iter().chain(&contract.files) {
+            let file = fs::canonicalize(file)
+                .unwrap_or_else(|_| env::current_dir().unwrap_or_default().join(file));
+            if let Some(dir) = file.parent() {
+                if !dirs.contains(dir) {
+                    match debouncer.watcher().watch(dir, RecursiveMode::NonRecursive) {
+                        Ok(()) => {
+                            dirs.insert(dir.to_path_buf());
+                        }
+                        Err(err) => report("error", &watch_error(err)),
+                    }
+                }
+            }
+            files.insert(file);
+        }
+
+        match result {
+            Ok(outputs) => {
+                for (path, buf) in outputs {
+                    let content = strip_now(&String::from_utf8_lossy(&buf));
+                    if last_outputs.get(&path) == Some(&content) {
+                        continue;
+                    }
+                    if let Some(path) = &path {
+                        let current = fs::read_to_string(path).unwrap_or_default();
+                        if strip_now(&current) != content {
+                            write_output(&buf, Some(path), false)?;
+                            eprintln!("updated `{}`", path.display());
+                        }
+                    } else {
+                        write_output(&buf, None, false)?;
+                    }
+                    last_outputs.insert(path, content);
+                }
+            }
+            Err(err) => report("error", &err),
+        }
+
+        loop {
+            match rx.recv() {
+                Ok(Ok(events)) if events.iter().any(|event| files.contains(&event.path)) => break,
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => report("error", &watch_error(err)),
+                Err(_) => return Ok(()),
+            }
+        }
+    }
 }
 
-fn emit_ts<W: Write>(buf: &mut W, args: EmitArgs) -> io::Result<()> {
-    let contract = args.contract();
+/// Writes `buf` to the file at `path`, or to stdout when there is none.
+/// When `check` is set, nothing is written, see `check_output`.
+fn write_output(buf: &[u8], path: Option<&Path>, check: bool) -> Result<bool> {
+    match path {
+        Some(path) if check => check_output(buf, path),
+        Some(path) => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, buf)?;
+            Ok(true)
+        }
+        None if check => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--check needs the file to check, given either by --output or --readme",
+        )
+        .into()),
+        None => {
+            stdout().write_all(buf)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Checks whether the contents of the file at `path` are the same as `buf`, ignoring date/time information.
+/// Otherwise, prints a unified diff of the changes needed to bring the file up to date.
+fn check_output(buf: &[u8], path: &Path) -> Result<bool> {
+    let current = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let current = strip_now(&current);
+    let expected = strip_now(&String::from_utf8_lossy(buf));
+    if current == expected {
+        return Ok(true);
+    }
+
+    eprintln!("error: `{}` is out of date", path.display());
+    stdout().write_all(unified_diff(&current, &expected, &path.to_string_lossy()).as_bytes())?;
+    Ok(false)
+}
+
+/// Removes the date/time information emitted by `EmitArgs::now` from `text`,
+/// *i.e.*, from the lines written by `ts_header`, `md_prelude` and `md_footer`.
+fn strip_now(text: &str) -> String {
+    // Splits each header line at the date/time placeholder into the text before and after it.
+    const NOW: &str = "\0";
+    let mut headers = Vec::new();
+    ts_header(&mut headers, NOW.to_string(), env!("CARGO_BIN_NAME")).unwrap();
+    md_prelude(&mut headers, NOW.to_string()).unwrap();
+    md_footer(&mut headers, env!("CARGO_BIN_NAME"), NOW.to_string()).unwrap();
+    let headers = String::from_utf8(headers).unwrap();
+    let headers: Vec<(&str, &str)> = headers
+        .lines()
+        .filter_map(|line| line.split_once(NOW))
+        .collect();
+
+    text.split_inclusive('\n')
+        .map(|line| {
+            let (content, end) = match line.strip_suffix('\n') {
+                Some(content) => (content, "\n"),
+                None => (line, ""),
+            };
+            for (before, after) in &headers {
+                let now = content
+                    .strip_prefix(before)
+                    .and_then(|rest| rest.strip_suffix(after));
+                if let Some(now) = now {
+                    if now.starts_with(" on ") {
+                        return format!("{}{}{}", before, after, end);
+                    }
+                }
+            }
+            line.to_string()
+        })
+        .collect()
+}
+
+/// Returns the unified diff, with 3 lines of context, to turn `old` into `new`.
+fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(path, path)
+        .to_string()
+}
 
-    ts_prelude(buf, args.now(), env!("CARGO_BIN_NAME"))?;
-    ts_items(buf, &contract)?;
-    ts_extend_traits(buf, &contract)?;
-    ts_contract_methods(buf, &contract)?;
+/// Prints `err` to `stderr` in the style of `rustc` diagnostics at the given `level`,
+/// *i.e.*, `error` or `warning`, including the offending source line when its location is known.
+fn report(level: &str, err: &Error) {
+    eprintln!("{}: {}", level, err);
+    if let Some(location) = err.location() {
+        let gutter = " ".repeat(location.line.to_string().len());
+        eprintln!("{}--> {}", gutter, location);
+
+        let src = location
+            .file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok());
+        if let Some(line) = src
+            .as_ref()
+            .and_then(|src| src.lines().nth(location.line - 1))
+        {
+            eprintln!("{} |", gutter);
+            eprintln!("{} | {}", location.line, line);
+            eprintln!("{} | {}^", gutter, " ".repeat(location.column - 1));
+        }
+    }
+}
+
+fn emit_ts<W: Write>(buf: &mut W, args: &TSEmitArgs, contract: &Contract) -> Result<()> {
+    let options = args.options();
+    if args.prelude_only {
+        let options = TsOptions {
+            sdk: options.sdk,
+            types: options.types,
+            ..Default::default()
+        };
+        ts_prelude(buf, args.emit_args.now(), env!("CARGO_BIN_NAME"), &options)?;
+        return Ok(());
+    }
+
+    ts_prelude(buf, args.emit_args.now(), env!("CARGO_BIN_NAME"), &options)?;
+    ts_type_overrides(buf, &options)?;
+    ts_items(buf, contract)?;
+    ts_extend_traits(buf, contract)?;
+    ts_contract_methods(buf, contract)?;
+    if options.factory {
+        ts_contract_factory(buf, contract)?;
+    }
+    if options.actions {
+        ts_contract_actions(buf, contract)?;
+    }
+    ts_ext_contracts(buf, contract)?;
+    if options.zod {
+        zod_prelude(buf, options.sdk)?;
+        zod_type_overrides(buf, &options)?;
+        zod_items(buf, contract)?;
+        zod_result_schemas(buf, contract)?;
+    }
 
     Ok(())
 }
 
-fn emit_md<W: Write>(buf: &mut W, args: &EmitArgs) -> io::Result<()> {
-    let now = args.now();
-    let contract = args.contract();
+/// Emits the bindings split into the `types.ts`, `contract.ts` and `index.ts` files, see `TSEmitArgs::split`.
+/// The SDK types are exported by `types.ts`, unless they are imported from `--prelude-from`.
+fn emit_ts_split(args: &TSEmitArgs, contract: &Contract) -> Result<Vec<(&'static str, Vec<u8>)>> {
+    let options = args.options();
+    let now = args.emit_args.now();
+    let bin_name = env!("CARGO_BIN_NAME");
+
+    let mut types = Vec::new();
+    ts_type_overrides(&mut types, &options)?;
+    ts_type_items(&mut types, contract)?;
+    if options.zod {
+        zod_prelude(&mut types, options.sdk)?;
+        zod_type_overrides(&mut types, &options)?;
+        zod_items(&mut types, contract)?;
+    }
+
+    let mut types_ts = Vec::new();
+    let types_options = TsOptions {
+        factory: false,
+        actions: false,
+        ..options.clone()
+    };
+    ts_prelude(&mut types_ts, now.clone(), bin_name, &types_options)?;
+    types_ts.extend(&types);
+
+    let mut contract_ts = Vec::new();
+    let contract_options = TsOptions {
+        prelude_module: Some(
+            options
+                .prelude_module
+                .clone()
+                .unwrap_or_else(|| "./types".to_string()),
+        ),
+        // Overridden SDK types are declared by `types.ts` itself, unless imported from `--prelude-from`.
+        types: if options.prelude_module.is_some() {
+            options.types.clone()
+        } else {
+            Vec::new()
+        },
+        ..options.clone()
+    };
+    ts_prelude(&mut contract_ts, now.clone(), bin_name, &contract_options)?;
+    ts_import_exports(
+        &mut contract_ts,
+        &String::from_utf8_lossy(&types),
+        "./types",
+    )?;
+    ts_impl_items(&mut contract_ts, contract)?;
+    ts_extend_traits(&mut contract_ts, contract)?;
+    ts_contract_methods(&mut contract_ts, contract)?;
+    if options.factory {
+        ts_contract_factory(&mut contract_ts, contract)?;
+    }
+    if options.actions {
+        ts_contract_actions(&mut contract_ts, contract)?;
+    }
+    ts_ext_contracts(&mut contract_ts, contract)?;
+    if options.zod {
+        zod_result_schemas(&mut contract_ts, contract)?;
+    }
+
+    let mut index_ts = Vec::new();
+    ts_header(&mut index_ts, now, bin_name)?;
+    writeln!(index_ts, "export * from \"./types\";")?;
+    writeln!(index_ts, "export * from \"./contract\";")?;
 
+    Ok(vec![
+        ("types.ts", types_ts),
+        ("contract.ts", contract_ts),
+        ("index.ts", index_ts),
+    ])
+}
+
+fn emit_md<W: Write>(buf: &mut W, args: &EmitArgs, contract: &Contract) -> Result<()> {
+    let now = args.now();
     md_prelude(buf, now.clone())?;
-    md_methods_table(buf, &contract)?;
-    md_items(buf, &contract)?;
+    md_methods_table(buf, contract)?;
+    md_items(buf, contract)?;
 
     md_footer(buf, env!("CARGO_BIN_NAME"), now)?;
 
     Ok(())
 }
 
-fn emit_md_table<W: Write>(buf: &mut W, args: &EmitArgs, content: String) -> io::Result<()> {
-    let contract = args.contract();
+fn emit_abi<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *buf, &abi_root(contract)?).map_err(io::Error::from)?;
+    writeln!(buf)?;
 
-    let mut is_open = false;
-    for line in content.lines() {
-        let line = line.trim_end().to_string();
-        if line == "<!-- AUTOGENERATED near-syn:methods-table:start, DO NOT MODIFY -->" {
-            is_open = true;
-        } else if line == "<!-- AUTOGENERATED near-syn:methods-table:end, DO NOT MODIFY -->"
-            && is_open
-        {
-            writeln!(
-                buf,
-                "<!-- AUTOGENERATED near-syn:methods-table:start, DO NOT MODIFY -->"
-            )?;
-            md_methods_table(buf, &contract).unwrap();
-            writeln!(
-                buf,
-                "<!-- AUTOGENERATED near-syn:methods-table:end, DO NOT MODIFY -->"
-            )?;
-            is_open = false;
-        } else if !is_open {
+    Ok(())
+}
+
+fn emit_schema<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *buf, &schema_root(contract)?).map_err(io::Error::from)?;
+    writeln!(buf)?;
+
+    Ok(())
+}
+
+/// Regenerates the sections of the README `content` delimited by `near-syn` markers, see `md_section`.
+/// A section whose start marker has no matching end marker, whose name is not known,
+/// or whose trait is not implemented by the contract, is left unchanged.
+/// When `sections` is not empty, only the sections listed are regenerated.
+/// The start and end markers are given by templates where `{section}` stands for the section name.
+fn emit_md_readme<W: Write>(
+    buf: &mut W,
+    contract: &Contract,
+    readme: &str,
+    content: String,
+    sections: &[String],
+    (start_marker, end_marker): (&str, &str),
+) -> Result<()> {
+    fn marker(line: &str, template: &str) -> Option<String> {
+        let (before, after) = template.split_once(MD_SECTION)?;
+        line.strip_prefix(before)?
+            .strip_suffix(after)
+            .map(str::to_string)
+    }
+
+    let mut open: Option<(String, usize, Vec<&str>)> = None;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        match &mut open {
+            None => {
+                if let Some(section) = marker(line, start_marker) {
+                    open = Some((section, i + 1, Vec::new()));
+                }
+                writeln!(buf, "{}", line)?;
+            }
+            Some((section, start, lines)) if marker(line, end_marker).as_ref() == Some(section) => {
+                if !sections.is_empty() && !sections.contains(section) {
+                    for line in lines {
+                        writeln!(buf, "{}", line)?;
+                    }
+                } else if !md_section(buf, contract, section)? {
+                    let marker = start_marker.replace(MD_SECTION, section);
+                    match section.strip_prefix("methods:") {
+                        Some(trait_name) => eprintln!(
+                            "warning: {}:{}: trait `{}` of section `{}` is not implemented by the contract, leaving it unchanged",
+                            readme, start, trait_name, marker
+                        ),
+                        None => eprintln!(
+                            "warning: {}:{}: unknown section `{}`, leaving it unchanged",
+                            readme, start, marker
+                        ),
+                    }
+                    for line in lines {
+                        writeln!(buf, "{}", line)?;
+                    }
+                }
+                writeln!(buf, "{}", line)?;
+                open = None;
+            }
+            Some((_, _, lines)) => lines.push(line),
+        }
+    }
+
+    if let Some((section, line, lines)) = open {
+        eprintln!(
+            "warning: {}:{}: start marker `{}` has no matching end marker, leaving the rest of the file unchanged",
+            readme,
+            line,
+            start_marker.replace(MD_SECTION, &section)
+        );
+        for line in lines {
             writeln!(buf, "{}", line)?;
         }
     }
-    Ok(())
-}
 
-/// Returns the Rust syntax tree for the given `file_name` path.
-/// Panics if the file cannot be open or the file has syntax errors.
-///
-/// ## Example
-///
-/// ```no_run
-/// let mut ts = near_syn::ts::TS::new(std::io::stdout());
-/// let ast = near_syn::parse_rust("path/to/file.rs");
-/// ts.ts_items(&ast.items);
-/// ```
-fn parse_rust<S: AsRef<Path>>(file_name: S) -> syn::File {
-    let mut file = File::open(file_name).expect("Unable to open file");
-    let mut src = String::new();
-    file.read_to_string(&mut src).expect("Unable to read file");
-
-    syn::parse_file(&src).expect("Unable to parse file")
+    Ok(())
 }
@@ -2,14 +2,25 @@
 
 use std::io::{self, Write};
 
-use syn::ImplItemMethod;
+use quote::ToTokens;
+use syn::{ImplItemMethod, ItemImpl, ReturnType, Type};
 
 use crate::{
     contract::{Contract, NearItem, NearItemTrait},
-    near_sdk_syn::{get_docs, write_docs, NearImpl, NearMethod},
-    ts::{ts_ret_type, ts_sig},
+    error::Result,
+    near_sdk_syn::{get_docs, result_types, type_name, write_docs, NearImpl, NearMethod},
+    ts::{ts_enum, ts_method_ret_type, ts_sig, ts_struct, ts_type, ts_typedef},
 };
 
+/// Placeholder of the section name in the README markers, see `md_section`.
+pub const MD_SECTION: &str = "{section}";
+
+/// Default template of the line starting a section of a README, see `md_section`.
+pub const MD_START_MARKER: &str = "<!-- AUTOGENERATED near-syn:{section}:start, DO NOT MODIFY -->";
+
+/// Default template of the line ending a section of a README, see `md_section`.
+pub const MD_END_MARKER: &str = "<!-- AUTOGENERATED near-syn:{section}:end, DO NOT MODIFY -->";
+
 ///
 pub fn md_prelude<W: Write>(buf: &mut W, now: String) -> io::Result<()> {
     writeln!(buf, "<!-- AUTOGENERATED doc{}, do not modify! -->", now)?;
@@ -51,25 +62,26 @@ pub fn md_footer<W: Write>(buf: &mut W, bin: &str, now: String) -> io::Result<()
 }
 
 ///
-pub fn md_methods_table<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
+pub fn md_methods_table<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
     fn write_section<W: Write>(
         buf: &mut W,
         title: &str,
         methods: &Vec<String>,
         contract: &Contract,
-    ) -> io::Result<()> {
+    ) -> Result<()> {
         writeln!(buf, "> **{}**", title)?;
         writeln!(buf, "")?;
         writeln!(buf, "| Method | Description | Return |")?;
         writeln!(buf, "| ------ | ----------- | ------ |")?;
         for name in methods {
-            if let Some((method, impl_item)) = contract.methods.get(name) {
+            if let Some((method, impl_item, file)) = contract.methods.get(name) {
                 let item_trait = if let Some(trait_name) = impl_item.get_trait_name() {
                     contract.traits.get(&trait_name)
                 } else {
                     None
                 };
-                md_methods_table_row(buf, &method, item_trait)?;
+                md_methods_table_row(buf, method, item_trait)
+                    .map_err(|err| err.in_file(file.as_deref()))?;
             }
         }
         writeln!(buf, "")?;
@@ -78,55 +90,267 @@ pub fn md_methods_table<W: Write>(buf: &mut W, contract: &Contract) -> io::Resul
     }
 
     write_section(buf, "Init Methods", &contract.init_methods, contract)?;
-    write_section(buf, "View Methods", &contract.view_methods, contract)?;
-    write_section(buf, "Change Methods", &contract.change_methods, contract)?;
+    // Callbacks are listed in their own section.
+    let not_callback = |methods: &Vec<String>| {
+        methods
+            .iter()
+            .filter(|name| !contract.callbacks.contains(name))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    write_section(
+        buf,
+        "View Methods",
+        &not_callback(&contract.view_methods),
+        contract,
+    )?;
+    write_section(
+        buf,
+        "Change Methods",
+        &not_callback(&contract.change_methods),
+        contract,
+    )?;
+    if !contract.callbacks.is_empty() {
+        write_section(buf, "Callbacks", &contract.callbacks, contract)?;
+    }
 
     Ok(())
 }
 
+/// Writes the row of `method` in the methods table.
+/// Borsh-serialized arguments and results are flagged with _(Borsh)_.
 ///
+/// ```
+/// let mut buf = Vec::new();
+/// let method = syn::parse_str("#[result_serializer(borsh)] pub fn get(&self, #[serializer(borsh)] key: Vec<u8>) -> Vec<u8> {}").unwrap();
+/// near_syn::md::md_methods_table_row(&mut buf, &method, None).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), "| :eyeglasses: `get` _(Borsh args)_ |  | `Uint8Array` _(Borsh)_ |\n");
+/// ```
 pub fn md_methods_table_row<W: Write>(
     buf: &mut W,
     method: &ImplItemMethod,
     item_trait: Option<&NearItemTrait>,
-) -> io::Result<()> {
+) -> Result<()> {
     let (mut_mod, init_decl) = method.mods();
     let docs = get_docs(&method.join_attrs(item_trait)).join(" ");
+    let args_decl = if method.is_borsh_args() {
+        " _(Borsh args)_"
+    } else {
+        ""
+    };
+    let ret_decl = if method.is_borsh_result() {
+        " _(Borsh)_"
+    } else {
+        ""
+    };
 
     writeln!(
         buf,
-        "| {} `{}`{} | {} | `{}` |",
+        "| {} `{}`{}{} | {} | `{}`{} |",
         mut_mod,
         method.sig.ident,
         init_decl,
+        args_decl,
         docs,
-        ts_ret_type(&method.sig.output).replace('|', "\\|"),
+        ts_method_ret_type(method)?.replace('|', "\\|"),
+        ret_decl,
     )?;
 
     Ok(())
 }
 
 ///
-pub fn md_items<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
+pub fn md_items<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
     write_docs(buf, &contract.top_level_attrs, |l| l.trim().to_string())?;
 
     for item in &contract.items {
-        if let NearItem::Impl(impl_item) = item {
-            if let Some(methods) = impl_item.bindgen_methods() {
-                let item_trait = if let Some(trait_name) = impl_item.get_trait_name() {
-                    writeln!(buf, "\n## Methods for `{}` interface", trait_name)?;
+        if let NearItem::Impl(impl_item) = &**item {
+            md_impl(buf, impl_item, contract).map_err(|err| err.in_file(item.file.as_deref()))?;
+        }
+    }
 
-                    contract.traits.get(&trait_name)
-                } else {
-                    if let Some(impl_name) = impl_item.get_impl_name() {
-                        writeln!(buf, "\n## Methods for {}", impl_name)?;
-                    } else {
-                        writeln!(buf, "\n## Methods for Contract")?;
-                    }
-                    None
-                };
+    Ok(())
+}
+
+/// Writes the methods and callbacks of `impl_item`, each under its own heading.
+fn md_impl<W: Write>(buf: &mut W, impl_item: &ItemImpl, contract: &Contract) -> Result<()> {
+    let trait_name = impl_item.get_trait_name();
+    let item_trait = trait_name
+        .as_ref()
+        .and_then(|trait_name| contract.traits.get(trait_name));
+
+    let methods = impl_item
+        .bindgen_methods()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|method| !method.is_callback())
+        .collect::<Vec<_>>();
+    if !methods.is_empty() {
+        if let Some(trait_name) = &trait_name {
+            writeln!(buf, "\n## Methods for `{}` interface", trait_name)?;
+        } else if let Some(impl_name) = impl_item.get_impl_name() {
+            writeln!(buf, "\n## Methods for {}", impl_name)?;
+        } else {
+            writeln!(buf, "\n## Methods for Contract")?;
+        }
+
+        md_methods(buf, methods, item_trait, contract)?;
+    }
+
+    let callbacks = impl_item.callback_methods();
+    if !callbacks.is_empty() {
+        if let Some(trait_name) = &trait_name {
+            writeln!(buf, "\n## Callbacks for `{}` interface", trait_name)?;
+        } else if let Some(impl_name) = impl_item.get_impl_name() {
+            writeln!(buf, "\n## Callbacks for {}", impl_name)?;
+        } else {
+            writeln!(buf, "\n## Callbacks for Contract")?;
+        }
+
+        md_methods(buf, callbacks, item_trait, contract)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the section of a README delimited by the `near-syn:{section}` markers.
+/// Returns `false`, without writing anything, when `section` is not known,
+/// or when it is `methods:{TraitName}` and `TraitName` is not implemented by the `contract`.
+/// The known sections are
+///
+/// - `methods-table`: the methods table, see `md_methods_table`.
+/// - `types`: the types of the contract, see `md_types`.
+/// - `init`: the docs of the init methods, see `md_init_methods`.
+/// - `methods:{TraitName}`: the docs of the methods implementing `TraitName`, see `md_trait_methods`.
+/// - `full-docs`: the methods table followed by the docs of all methods.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// let mut buf = Vec::new();
+/// assert!(near_syn::md::md_section(&mut buf, &contract, "full-docs").unwrap());
+/// assert!(!near_syn::md::md_section(&mut buf, &contract, "unknown").unwrap());
+/// assert!(!near_syn::md::md_section(&mut buf, &contract, "methods:NftCore").unwrap());
+/// ```
+pub fn md_section<W: Write>(buf: &mut W, contract: &Contract, section: &str) -> Result<bool> {
+    match section {
+        "methods-table" => md_methods_table(buf, contract)?,
+        "types" => md_types(buf, contract)?,
+        "init" => md_init_methods(buf, contract)?,
+        "full-docs" => {
+            md_methods_table(buf, contract)?;
+            md_items(buf, contract)?;
+        }
+        _ => match section.strip_prefix("methods:") {
+            Some(trait_name) if contract.interfaces.iter().any(|name| name == trait_name) => {
+                md_trait_methods(buf, contract, trait_name)?
+            }
+            _ => return Ok(false),
+        },
+    }
+
+    Ok(true)
+}
+
+/// Writes the docs of the `struct`s, `enum`s and type aliases of the `contract`,
+/// along with their TypeScript declaration.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     /// Identifier of a token.
+///     type TokenId = U64;
+///
+///     #[derive(Serialize)]
+///     struct Token {
+///         id: TokenId,
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::md::md_types(&mut buf, &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), [
+///     "",
+///     "### `TokenId`",
+///     "",
+///     "```typescript",
+///     "export type TokenId = U64;",
+///     "```",
+///     "",
+///     "Identifier of a token.",
+///     "",
+///     "### `Token`",
+///     "",
+///     "```typescript",
+///     "export type Token = {",
+///     "    /**",
+///     "     */",
+///     "    id: TokenId;",
+///     "",
+///     "}",
+///     "```",
+///     "",
+///     "",
+/// ].join("\n"));
+/// ```
+pub fn md_types<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    for item in &contract.items {
+        let mut decl = Vec::new();
+        let (ident, attrs) = match &**item {
+            NearItem::Impl(_) => continue,
+            NearItem::Struct(item_struct) => {
+                ts_struct(&mut decl, item_struct)
+                    .map_err(|err| err.in_file(item.file.as_deref()))?;
+                (&item_struct.ident, &item_struct.attrs)
+            }
+            NearItem::Enum(item_enum) => {
+                ts_enum(&mut decl, item_enum).map_err(|err| err.in_file(item.file.as_deref()))?;
+                (&item_enum.ident, &item_enum.attrs)
+            }
+            NearItem::Type(item_type) => {
+                ts_typedef(&mut decl, item_type)
+                    .map_err(|err| err.in_file(item.file.as_deref()))?;
+                (&item_type.ident, &item_type.attrs)
+            }
+        };
+
+        // The doc-comments are written in Markdown instead, so the leading JSDoc is dropped.
+        let decl = String::from_utf8_lossy(&decl);
+        let decl = match decl.find(" */\n") {
+            Some(i) if decl.starts_with("/**") => &decl[i + 4..],
+            _ => &decl,
+        };
+        writeln!(buf, "\n### `{}`\n", ident)?;
+        writeln!(buf, "```typescript\n{}\n```\n", decl.trim_end())?;
+        write_docs(buf, attrs, |l| l.trim().to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Writes the docs of the init methods of the `contract`.
+pub fn md_init_methods<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    for name in &contract.init_methods {
+        if let Some((method, impl_item, file)) = contract.methods.get(name) {
+            let item_trait = impl_item
+                .get_trait_name()
+                .and_then(|trait_name| contract.traits.get(&trait_name));
+            md_methods(buf, vec![method], item_trait, contract)
+                .map_err(|err| err.in_file(file.as_deref()))?;
+        }
+    }
+
+    Ok(())
+}
 
-                md_methods(buf, methods, item_trait)?;
+/// Writes the docs of the methods and callbacks of every `impl` of the trait named `trait_name`, see `md_items`.
+pub fn md_trait_methods<W: Write>(
+    buf: &mut W,
+    contract: &Contract,
+    trait_name: &str,
+) -> Result<()> {
+    for item in &contract.items {
+        if let NearItem::Impl(impl_item) = &**item {
+            if impl_item.get_trait_name().as_deref() == Some(trait_name) {
+                md_impl(buf, impl_item, contract)
+                    .map_err(|err| err.in_file(item.file.as_deref()))?;
             }
         }
     }
@@ -139,7 +363,8 @@ pub fn md_methods<W: Write>(
     buf: &mut W,
     methods: Vec<&ImplItemMethod>,
     item_trait: Option<&NearItemTrait>,
-) -> io::Result<()> {
+    contract: &Contract,
+) -> Result<()> {
     for method in methods {
         let (mut_mod, init_decl) = method.mods();
         writeln!(
@@ -147,10 +372,147 @@ pub fn md_methods<W: Write>(
             "\n### {} `{}`{}\n",
             mut_mod, method.sig.ident, init_decl
         )?;
-        writeln!(buf, "```typescript\n{}\n```\n", ts_sig(&method))?;
+        writeln!(buf, "```typescript\n{}\n```\n", ts_sig(method)?)?;
         write_docs(buf, &method.join_attrs(item_trait), |l| {
             l.trim().to_string()
         })?;
+
+        if method.is_callback() {
+            md_callback_args(buf, method)?;
+        }
+
+        if method.is_handle_result() {
+            if let ReturnType::Type(_, ty) = &method.sig.output {
+                if let Some((_, err_ty)) = result_types(ty) {
+                    md_errors(buf, err_ty, contract)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the "Promise results" section of a callback `method`,
+/// listing the promise result consumed by each of its callback parameters.
+///
+/// ```
+/// let method = syn::parse_str(r#"
+///     #[private]
+///     pub fn on_transfer(
+///         &mut self,
+///         #[callback_unwrap] amount: U128,
+///         #[callback_result] memo: Result<String, PromiseError>,
+///     ) {}"#).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::md::md_callback_args(&mut buf, &method).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), [
+///     "",
+///     "#### Promise results",
+///     "",
+///     "- `amount: U128`: fails when the promise fails (`callback_unwrap`)",
+///     "- `memo: string`: the promise result, or its `PromiseError` when the promise fails (`callback_result`)",
+///     "",
+/// ].join("\n"));
+/// ```
+pub fn md_callback_args<W: Write>(buf: &mut W, method: &ImplItemMethod) -> Result<()> {
+    writeln!(buf, "\n#### Promise results\n")?;
+    for (pat_type, attr_name) in method.callback_args() {
+        let (ty, description) = match attr_name {
+            "callback_result" => match result_types(&pat_type.ty) {
+                Some((ok, err)) => (
+                    ok,
+                    format!(
+                        "the promise result, or its `{}` when the promise fails",
+                        err.to_token_stream()
+                    ),
+                ),
+                None => (&*pat_type.ty, "the promise result or its error".to_string()),
+            },
+            "callback_vec" => (&*pat_type.ty, "the results of all promises".to_string()),
+            _ => (&*pat_type.ty, "fails when the promise fails".to_string()),
+        };
+        writeln!(
+            buf,
+            "- `{}: {}`: {} (`{}`)",
+            pat_type.pat.to_token_stream(),
+            ts_type(ty)?,
+            description,
+            attr_name
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes the "Errors" section of a `#[handle_result]` method failing with `err_ty`.
+/// When `err_ty` is declared in the `contract`, its doc-comments are included,
+/// and in the case of an `enum`, each variant is listed along with its doc-comments.
+/// Qualified paths, *e.g.*, `crate::errors::ContractError`, are looked up by their last segment.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     /// Errors of the contract.
+///     #[derive(FunctionError)]
+///     enum ContractError {
+///         /// The token does not exist.
+///         NotFound,
+///         Unauthorized,
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::md::md_errors(&mut buf, &syn::parse_str("ContractError").unwrap(), &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), [
+///     "",
+///     "#### Errors",
+///     "",
+///     "Fails with `ContractError`.",
+///     "",
+///     "Errors of the contract.",
+///     "",
+///     "- `NotFound`: The token does not exist.",
+///     "- `Unauthorized`",
+///     "",
+/// ].join("\n"));
+///
+/// let mut qualified = Vec::new();
+/// near_syn::md::md_errors(&mut qualified, &syn::parse_str("crate::errors::ContractError").unwrap(), &contract).unwrap();
+/// assert_eq!(qualified, buf);
+/// ```
+pub fn md_errors<W: Write>(buf: &mut W, err_ty: &Type, contract: &Contract) -> io::Result<()> {
+    let name = match err_ty {
+        Type::Path(p) => type_name(p),
+        _ => err_ty.to_token_stream().to_string(),
+    };
+    writeln!(buf, "\n#### Errors\n")?;
+    writeln!(buf, "Fails with `{}`.", name)?;
+
+    let (attrs, variants) = match contract.find_type(&name) {
+        Some(NearItem::Struct(item_struct)) => (&item_struct.attrs, None),
+        Some(NearItem::Enum(item_enum)) => (&item_enum.attrs, Some(&item_enum.variants)),
+        Some(NearItem::Type(item_type)) => (&item_type.attrs, None),
+        Some(NearItem::Impl(_)) | None => return Ok(()),
+    };
+
+    let docs = get_docs(attrs);
+    if !docs.is_empty() {
+        writeln!(buf)?;
+        for line in docs {
+            writeln!(buf, "{}", line.trim())?;
+        }
+    }
+
+    if let Some(variants) = variants {
+        writeln!(buf)?;
+        for variant in variants {
+            let docs = get_docs(&variant.attrs);
+            if docs.is_empty() {
+                writeln!(buf, "- `{}`", variant.ident)?;
+            } else {
+                let docs = docs.iter().map(|line| line.trim()).collect::<Vec<_>>();
+                writeln!(buf, "- `{}`: {}", variant.ident, docs.join(" "))?;
+            }
+        }
     }
 
     Ok(())
@@ -180,6 +542,8 @@ impl MarkdownMethod for ImplItemMethod {
     fn mods(&self) -> (&str, &str) {
         if self.is_init() {
             (":rocket:", " (_constructor_)")
+        } else if self.is_callback() {
+            (self.mut_mod(), " (_callback_)")
         } else {
             (self.mut_mod(), "")
         }
@@ -3,12 +3,18 @@
 
 use std::io::{self, Write};
 
+use proc_macro2::TokenStream;
 use syn::{
-    Attribute, FnArg, ImplItem, ImplItemMethod, ItemEnum, ItemImpl, ItemStruct, Lit, Meta,
-    MetaList, MetaNameValue, NestedMeta, Path, PathArguments, Type, Visibility,
+    bracketed, ext::IdentExt, parenthesized, parse::ParseStream, token, Attribute, Expr, ExprLit,
+    Field, FieldsNamed, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod, ItemEnum,
+    ItemImpl, ItemStruct, ItemTrait, Lit, Meta, MetaList, MetaNameValue, NestedMeta, PatType, Path,
+    PathArguments, Token, Type, TypeArray, TypePath, Variant, Visibility,
 };
 
-use crate::contract::NearItemTrait;
+use crate::{
+    contract::NearItemTrait,
+    error::{Error, Location, Result},
+};
 
 ///
 pub trait NearBindgen {
@@ -25,12 +31,41 @@ pub trait NearBindgen {
     ///     // methods...
     /// }
     /// ```
+    ///
+    /// Since near-sdk 5, `impl` items are marked with `#[near]` instead,
+    /// and the contract `struct` with `#[near(contract_state)]`.
+    ///
+    /// ```
+    /// use near_syn::NearBindgen;
+    /// use syn::{parse_str, ItemImpl, ItemStruct};
+    ///
+    /// let item_impl: ItemImpl = parse_str("#[near] impl Counter {}").unwrap();
+    /// assert!(item_impl.is_bindgen());
+    ///
+    /// let item_struct: ItemStruct = parse_str("#[near(contract_state)] struct Counter {}").unwrap();
+    /// assert!(item_struct.is_bindgen());
+    ///
+    /// let item_struct: ItemStruct = parse_str("#[near] struct Counter {}").unwrap();
+    /// assert!(!item_struct.is_bindgen());
+    /// ```
     fn is_bindgen(&self) -> bool;
 }
 
-impl<I: NearAttributable> NearBindgen for I {
+impl NearBindgen for ItemImpl {
     fn is_bindgen(&self) -> bool {
-        has_attr(&self.attrs(), "near_bindgen")
+        has_attr(&self.attrs, "near_bindgen") || has_attr(&self.attrs, "near")
+    }
+}
+
+impl NearBindgen for ItemStruct {
+    fn is_bindgen(&self) -> bool {
+        has_attr(&self.attrs, "near_bindgen") || has_near_arg(&self.attrs, "contract_state")
+    }
+}
+
+impl NearBindgen for ItemEnum {
+    fn is_bindgen(&self) -> bool {
+        has_attr(&self.attrs, "near_bindgen") || has_near_arg(&self.attrs, "contract_state")
     }
 }
 
@@ -53,11 +88,18 @@ pub trait NearImpl {
     fn get_impl_name(&self) -> Option<String>;
 
     /// Returns whether the given `self` implementation has any exported method.
+    /// `private` methods are not included, since only the contract itself can call them.
+    /// Thus, `private` callbacks are only returned by `callback_methods`.
     ///
     /// For more info on public method in the NEAR SDK,
     /// see <https://www.near-sdk.io/contract-interface/public-methods>.
     fn exported_methods(&self) -> Vec<&ImplItemMethod>;
 
+    /// Returns the callback methods if `self` `is_bindgen`, whether `private` or not.
+    /// Callback methods receive the result of a promise in their callback parameters,
+    /// see `NearMethod::callback_args`.
+    fn callback_methods(&self) -> Vec<&ImplItemMethod>;
+
     /// Returns the exported methods if `self` `is_bindgen`.
     /// In this case, the inner `Vec` should contain elements.
     /// Otherwise, it returns `None`.
@@ -99,6 +141,21 @@ impl NearImpl for ItemImpl {
         methods
     }
 
+    fn callback_methods(&self) -> Vec<&ImplItemMethod> {
+        let mut methods = Vec::new();
+        if self.is_bindgen() {
+            for impl_item in self.items.iter() {
+                if let ImplItem::Method(method) = impl_item {
+                    if (method.is_public() || self.trait_.is_some()) && method.is_callback() {
+                        methods.push(method);
+                    }
+                }
+            }
+        }
+
+        methods
+    }
+
     fn bindgen_methods(&self) -> Option<Vec<&ImplItemMethod>> {
         let methods = self.exported_methods();
         if self.is_bindgen() && methods.len() > 0 {
@@ -136,6 +193,63 @@ pub trait NearMethod {
     /// Returns whether the given `self` method is marked as `private`.
     fn is_private(&self) -> bool;
 
+    /// Returns whether the given `self` method is marked as `handle_result`,
+    /// *i.e.*, it returns a `Result<T, E>` where `Err` makes the call fail.
+    ///
+    /// ```
+    /// use near_syn::NearMethod;
+    ///
+    /// let method: syn::ImplItemMethod = syn::parse_str(
+    ///     "#[handle_result] pub fn f(&self) -> Result<u32, Error> { Ok(1) }").unwrap();
+    /// assert!(method.is_handle_result());
+    /// ```
+    fn is_handle_result(&self) -> bool;
+
+    /// Returns whether the arguments of the given `self` method are serialized with Borsh,
+    /// *i.e.*, any of its arguments is marked as `serializer(borsh)`.
+    ///
+    /// ```
+    /// use near_syn::NearMethod;
+    ///
+    /// let method: syn::ImplItemMethod = syn::parse_str(
+    ///     "pub fn f(&mut self, #[serializer(borsh)] data: Vec<u8>) {}").unwrap();
+    /// assert!(method.is_borsh_args());
+    /// ```
+    fn is_borsh_args(&self) -> bool;
+
+    /// Returns whether the result of the given `self` method is serialized with Borsh,
+    /// *i.e.*, it is marked as `result_serializer(borsh)`.
+    ///
+    /// ```
+    /// use near_syn::NearMethod;
+    ///
+    /// let method: syn::ImplItemMethod = syn::parse_str(
+    ///     "#[result_serializer(borsh)] pub fn f(&self) -> Vec<u8> { vec![] }").unwrap();
+    /// assert!(method.is_borsh_result());
+    /// ```
+    fn is_borsh_result(&self) -> bool;
+
+    /// Returns the arguments of the given `self` method that are part of the call input,
+    /// *i.e.*, excluding the callback parameters.
+    fn input_args(&self) -> Vec<&PatType>;
+
+    /// Returns the callback parameters of the given `self` method along with their attribute,
+    /// see `callback_attr`.
+    ///
+    /// ```
+    /// use near_syn::NearMethod;
+    ///
+    /// let method: syn::ImplItemMethod = syn::parse_str(
+    ///     "#[private] pub fn on_transfer(&mut self, sender_id: AccountId, #[callback_unwrap] amount: U128) {}").unwrap();
+    /// assert!(method.is_callback());
+    /// assert_eq!(method.input_args().len(), 1);
+    /// assert_eq!(method.callback_args()[0].1, "callback_unwrap");
+    /// ```
+    fn callback_args(&self) -> Vec<(&PatType, &'static str)>;
+
+    /// Returns whether the given `self` method has any callback parameter.
+    fn is_callback(&self) -> bool;
+
     /// Returns whether the given `self` method in `input` impl is being exported.
     fn is_exported(&self, input: &ItemImpl) -> bool;
 
@@ -172,6 +286,49 @@ impl NearMethod for ImplItemMethod {
         has_attr(&self.attrs, "private")
     }
 
+    fn is_handle_result(&self) -> bool {
+        has_attr(&self.attrs, "handle_result")
+    }
+
+    fn is_borsh_args(&self) -> bool {
+        self.sig.inputs.iter().any(|arg| match arg {
+            FnArg::Typed(pat_type) => is_borsh_serializer(&pat_type.attrs, "serializer"),
+            FnArg::Receiver(_) => false,
+        })
+    }
+
+    fn is_borsh_result(&self) -> bool {
+        is_borsh_serializer(&self.attrs, "result_serializer")
+    }
+
+    fn input_args(&self) -> Vec<&PatType> {
+        let mut args = Vec::new();
+        for arg in &self.sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                if callback_attr(pat_type).is_none() {
+                    args.push(pat_type);
+                }
+            }
+        }
+        args
+    }
+
+    fn callback_args(&self) -> Vec<(&PatType, &'static str)> {
+        let mut args = Vec::new();
+        for arg in &self.sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                if let Some(attr_name) = callback_attr(pat_type) {
+                    args.push((pat_type, attr_name));
+                }
+            }
+        }
+        args
+    }
+
+    fn is_callback(&self) -> bool {
+        !self.callback_args().is_empty()
+    }
+
     fn is_exported(&self, input: &ItemImpl) -> bool {
         (self.is_public() || input.trait_.is_some()) && !self.is_private()
     }
@@ -192,6 +349,20 @@ impl NearMethod for ImplItemMethod {
 }
 
 /// Defines methods to deal with serde's declarations in `struct`s or `enum`s.
+///
+/// Besides deriving `Serialize` and `Deserialize`,
+/// near-sdk 5 items declare the JSON serializer with `#[near(serializers = [json])]`.
+///
+/// ```
+/// use near_syn::NearSerde;
+/// use syn::{parse_str, ItemStruct};
+///
+/// let item_struct: ItemStruct = parse_str("#[near(serializers = [borsh, json])] struct S {}").unwrap();
+/// assert!(item_struct.is_serialize() && item_struct.is_deserialize());
+///
+/// let item_struct: ItemStruct = parse_str("#[near(serializers = [borsh])] struct S {}").unwrap();
+/// assert!(!item_struct.is_serde());
+/// ```
 pub trait NearSerde {
     /// Returns whether the given `self` item derives `serde::Serialize`.
     fn is_serialize(&self) -> bool;
@@ -205,11 +376,11 @@ pub trait NearSerde {
 
 impl<I: NearAttributable> NearSerde for I {
     fn is_serialize(&self) -> bool {
-        derives(&self.attrs(), "Serialize")
+        derives(self.attrs(), "Serialize") || near_serializers(self.attrs(), "json")
     }
 
     fn is_deserialize(&self) -> bool {
-        derives(&self.attrs(), "Deserialize")
+        derives(self.attrs(), "Deserialize") || near_serializers(self.attrs(), "json")
     }
 
     fn is_serde(&self) -> bool {
@@ -217,6 +388,57 @@ impl<I: NearAttributable> NearSerde for I {
     }
 }
 
+/// Defines methods to deal with `trait`s declaring the interface of external contracts.
+pub trait NearExtContract {
+    /// Returns the name given by `#[ext_contract(name)]`,
+    /// if the given `self` trait is marked as `ext_contract`.
+    /// When no name is given, the trait name is used instead.
+    ///
+    /// ```
+    /// use near_syn::NearExtContract;
+    ///
+    /// let item_trait: syn::ItemTrait = syn::parse_str("#[ext_contract(ext_ft)] trait FungibleToken {}").unwrap();
+    /// assert_eq!(item_trait.ext_contract_name().unwrap(), "ext_ft");
+    ///
+    /// let item_trait: syn::ItemTrait = syn::parse_str("#[ext_contract] trait FungibleToken {}").unwrap();
+    /// assert_eq!(item_trait.ext_contract_name().unwrap(), "FungibleToken");
+    ///
+    /// let item_trait: syn::ItemTrait = syn::parse_str("trait FungibleToken {}").unwrap();
+    /// assert_eq!(item_trait.ext_contract_name(), None);
+    /// ```
+    fn ext_contract_name(&self) -> Option<Ident>;
+}
+
+impl NearExtContract for ItemTrait {
+    fn ext_contract_name(&self) -> Option<Ident> {
+        for attr in &self.attrs {
+            if is_ident(&attr.path, "ext_contract") {
+                if let Ok(Meta::List(MetaList { nested, .. })) = attr.parse_meta() {
+                    if let Some(NestedMeta::Meta(Meta::Path(path))) = nested.first() {
+                        if let Some(ident) = path.get_ident() {
+                            return Some(ident.clone());
+                        }
+                    }
+                }
+                return Some(self.ident.clone());
+            }
+        }
+        None
+    }
+}
+
+/// Defines methods to deal with errors returned by `#[handle_result]` methods.
+pub trait NearFunctionError {
+    /// Returns whether the given `self` item derives `near_sdk::FunctionError`.
+    fn is_function_error(&self) -> bool;
+}
+
+impl<I: NearAttributable> NearFunctionError for I {
+    fn is_function_error(&self) -> bool {
+        derives(self.attrs(), "FunctionError")
+    }
+}
+
 /// Any Rust item, *e.g.*, `struct` or `enum` to which attributes can attached to.
 pub trait NearAttributable {
     /// The attributes of this item.
@@ -252,6 +474,120 @@ fn has_attr(attrs: &Vec<Attribute>, attr_name: &str) -> bool {
     false
 }
 
+/// Returns the arguments of the `#[near(...)]` attributes in `attrs`.
+/// Each argument is returned as its name together with the listed values, if any,
+/// *e.g.*, `#[near(contract_state, serializers = [borsh, json])]` returns
+/// `contract_state` with no values and `serializers` with `borsh` and `json`.
+///
+/// The `#[near]` arguments cannot be parsed with `Attribute::parse_meta`,
+/// since lists in brackets are not valid `Meta` items.
+fn near_args(attrs: &Vec<Attribute>) -> Vec<(String, Vec<String>)> {
+    fn parse_args(input: ParseStream) -> syn::Result<Vec<(String, Vec<String>)>> {
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            let name = input.call(Ident::parse_any)?.to_string();
+            let mut values = Vec::new();
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                if input.peek(token::Bracket) {
+                    let content;
+                    bracketed!(content in input);
+                    while !content.is_empty() {
+                        values.push(content.call(Ident::parse_any)?.to_string());
+                        if content.peek(token::Paren) {
+                            let options;
+                            parenthesized!(options in content);
+                            options.parse::<TokenStream>()?;
+                        }
+                        if !content.is_empty() {
+                            content.parse::<Token![,]>()?;
+                        }
+                    }
+                } else {
+                    input.parse::<Expr>()?;
+                }
+            } else if input.peek(token::Paren) {
+                let options;
+                parenthesized!(options in input);
+                options.parse::<TokenStream>()?;
+            }
+            args.push((name, values));
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+
+    let mut args = Vec::new();
+    for attr in attrs {
+        if is_ident(&attr.path, "near") && !attr.tokens.is_empty() {
+            if let Ok(attr_args) = attr.parse_args_with(parse_args) {
+                args.extend(attr_args);
+            }
+        }
+    }
+    args
+}
+
+/// Returns `true` if any `#[near(...)]` attribute in `attrs` has the argument `arg_name`.
+fn has_near_arg(attrs: &Vec<Attribute>, arg_name: &str) -> bool {
+    near_args(attrs).iter().any(|(name, _)| name == arg_name)
+}
+
+/// Returns `true` if any `#[near(serializers = [...])]` attribute in `attrs` lists `serializer`.
+fn near_serializers(attrs: &Vec<Attribute>, serializer: &str) -> bool {
+    near_args(attrs)
+        .iter()
+        .any(|(name, values)| name == "serializers" && values.iter().any(|v| v == serializer))
+}
+
+/// Attributes of the parameters receiving the result of promises in callback methods.
+const CALLBACK_ATTRS: [&str; 4] = [
+    "callback_unwrap",
+    "callback_result",
+    "callback_vec",
+    "callback",
+];
+
+/// Returns the callback attribute of the given method parameter, if any,
+/// *i.e.*, `callback_unwrap`, `callback_result`, `callback_vec` or `callback`.
+///
+/// ```
+/// use near_syn::callback_attr;
+///
+/// let arg: syn::FnArg = syn::parse_str("#[callback_result] result: Result<U128, PromiseError>").unwrap();
+/// if let syn::FnArg::Typed(pat_type) = arg {
+///     assert_eq!(callback_attr(&pat_type), Some("callback_result"));
+/// }
+/// ```
+pub fn callback_attr(pat_type: &PatType) -> Option<&'static str> {
+    CALLBACK_ATTRS
+        .iter()
+        .copied()
+        .find(|attr_name| has_attr(&pat_type.attrs, attr_name))
+}
+
+/// Returns `true` if any of the `attr_name` attributes in `attrs` selects the `borsh` serializer,
+/// *e.g.*, `#[serializer(borsh)]`.
+fn is_borsh_serializer(attrs: &Vec<Attribute>, attr_name: &str) -> bool {
+    for attr in attrs {
+        if is_ident(&attr.path, attr_name) {
+            if let Ok(Meta::List(MetaList { nested, .. })) = attr.parse_meta() {
+                for elem in nested {
+                    if let NestedMeta::Meta(Meta::Path(path)) = elem {
+                        if path.is_ident("borsh") {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
 /// Returns `true` if any of the attributes under item derive from `macro_name`.
 /// Returns `false` otherwise.
 fn derives(attrs: &Vec<Attribute>, macro_name: &str) -> bool {
@@ -302,8 +638,507 @@ pub fn join_path(path: &syn::Path) -> String {
         .join("::")
 }
 
+/// Types of the standard library and the NEAR SDK that qualified paths are resolved to by `type_name`,
+/// *e.g.*, `std::collections::HashMap` or `near_sdk::json_types::U128`.
+const KNOWN_TYPES: &[&str] = &[
+    "bool",
+    "i8",
+    "u8",
+    "i16",
+    "u16",
+    "i32",
+    "u32",
+    "i64",
+    "u64",
+    "i128",
+    "u128",
+    "isize",
+    "usize",
+    "f32",
+    "f64",
+    "char",
+    "str",
+    "String",
+    "Option",
+    "Result",
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "HashSet",
+    "BTreeSet",
+    "IndexSet",
+    "HashMap",
+    "BTreeMap",
+    "IndexMap",
+    "Box",
+    "Rc",
+    "Arc",
+    "Cow",
+    "U64",
+    "I64",
+    "U128",
+    "I128",
+    "Base64VecU8",
+    "Balance",
+    "AccountId",
+    "ValidAccountId",
+    "Gas",
+    "NearToken",
+    "PublicKey",
+    "Base58PublicKey",
+    "Timestamp",
+    "Duration",
+    "StorageUsage",
+    "CryptoHash",
+    "Base58CryptoHash",
+    "Promise",
+    "PromiseOrValue",
+];
+
+/// Returns the name of the type given by the path `p`, as matched when translating types.
+///
+/// Qualified paths are resolved by their last segment.
+/// Paths to `serde_json`'s `Value` are resolved to `serde_json::Value`.
+///
+/// ```
+/// use near_syn::type_name;
 ///
-pub fn get_docs(attrs: &Vec<Attribute>) -> Vec<String> {
+/// let name = |s| type_name(&syn::parse_str(s).unwrap());
+/// assert_eq!(name("Token"), "Token");
+/// assert_eq!(name("near_sdk::json_types::U128"), "U128");
+/// assert_eq!(name("std::collections::HashMap<K, V>"), "HashMap");
+/// assert_eq!(name("crate::token::Token"), "Token");
+/// assert_eq!(name("serde_json::Value"), "serde_json::Value");
+/// assert_eq!(name("other::Token"), "Token");
+/// ```
+///
+/// Paths resolved this way but not known to refer to the same type are reported by `unresolved_path`.
+pub fn type_name(p: &TypePath) -> String {
+    let path = &p.path;
+    let joined = join_path(path);
+    if joined == "serde_json::Value" || joined == "serde_json::value::Value" {
+        "serde_json::Value".to_string()
+    } else {
+        path.segments.last().unwrap().ident.to_string()
+    }
+}
+
+/// Returns an `Error::UnresolvedPath` when the qualified path `p` cannot be resolved,
+/// *i.e.*, its last segment is neither a known type of the standard library or the NEAR SDK,
+/// nor the path is relative to the crate itself.
+/// In that case, `type_name` falls back to the last segment, which might not be the intended type.
+///
+/// ```
+/// use near_syn::unresolved_path;
+///
+/// let unresolved = |s| unresolved_path(&syn::parse_str(s).unwrap());
+/// assert!(unresolved("Token").is_none());
+/// assert!(unresolved("near_sdk::json_types::U128").is_none());
+/// assert!(unresolved("crate::token::Token").is_none());
+/// assert_eq!(
+///     unresolved("other::Token").unwrap().to_string(),
+///     "cannot resolve type path `other::Token`, consider importing it with `use`",
+/// );
+/// ```
+pub fn unresolved_path(p: &TypePath) -> Option<Error> {
+    let path = &p.path;
+    if path.segments.len() == 1 && path.leading_colon.is_none() {
+        return None;
+    }
+
+    let joined = join_path(path);
+    let first = path.segments[0].ident.to_string();
+    let last = path.segments.last().unwrap().ident.to_string();
+    if type_name(p) == "serde_json::Value"
+        || p.qself.is_none()
+            && (KNOWN_TYPES.contains(&last.as_str())
+                || matches!(first.as_str(), "crate" | "self" | "super"))
+    {
+        None
+    } else {
+        Some(Error::UnresolvedPath {
+            path: joined,
+            location: Location::of(p),
+        })
+    }
+}
+
+/// Returns whether `ty` is a path to the type `name`, see `type_name`.
+pub fn is_type(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(p) if type_name(p) == name)
+}
+
+/// Returns the generic type arguments of the standard library type `name` given by `p`.
+/// `nargs` is the number of generic arguments `name` expects.
+///
+/// Returns an `Error::GenericArgs` when `p` has no generic arguments,
+/// when the number of arguments does not match `nargs`,
+/// or when any of the arguments is not a type, *e.g.*, a lifetime.
+/// This situation can only happen on Rust source files that were **not** type-checked by `rustc`.
+pub fn type_args<'a>(p: &'a TypePath, nargs: usize, name: &str) -> Result<Vec<&'a Type>> {
+    let error = |message: String| Error::GenericArgs {
+        message,
+        location: Location::of(p),
+    };
+
+    if let PathArguments::AngleBracketed(args) = &p.path.segments.last().unwrap().arguments {
+        if args.args.len() != nargs {
+            return Err(error(format!(
+                "{} expects {} generic(s) argument(s), found {}",
+                name,
+                nargs,
+                args.args.len()
+            )));
+        }
+        let mut result = Vec::new();
+        for arg in &args.args {
+            if let GenericArgument::Type(tk) = arg {
+                result.push(tk);
+            } else {
+                return Err(error(format!("No type provided for {}", name)));
+            }
+        }
+        Ok(result)
+    } else {
+        Err(error(format!("{} used with no generic arguments", name)))
+    }
+}
+
+/// Returns the `Ok` and `Err` types when `ty` is a `Result<T, E>`.
+///
+/// ```
+/// let ty = syn::parse_str("Result<U128, ContractError>").unwrap();
+/// let (ok, err) = near_syn::result_types(&ty).unwrap();
+/// assert_eq!(quote::quote!(#ok).to_string(), "U128");
+/// assert_eq!(quote::quote!(#err).to_string(), "ContractError");
+/// ```
+pub fn result_types(ty: &Type) -> Option<(&Type, &Type)> {
+    match ty {
+        Type::Path(p) if is_type(ty, "Result") => {
+            let targs = type_args(p, 2, "Result").ok()?;
+            Some((targs[0], targs[1]))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the type pointed to when `p` is a smart pointer,
+/// *i.e.*, `Box<T>`, `Rc<T>`, `Arc<T>` or `Cow<'a, T>`.
+/// `serde` serializes smart pointers as the value they point to.
+///
+/// ```
+/// let ty: syn::TypePath = syn::parse_str("Cow<'a, str>").unwrap();
+/// let pointee = near_syn::pointee_type(&ty).unwrap().unwrap();
+/// assert_eq!(quote::quote!(#pointee).to_string(), "str");
+/// ```
+pub fn pointee_type(p: &TypePath) -> Result<Option<&Type>> {
+    Ok(match type_name(p).as_str() {
+        name @ ("Box" | "Rc" | "Arc") => Some(type_args(p, 1, name)?[0]),
+        "Cow" => match &p.path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(args) => {
+                let tys = args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect::<Vec<&Type>>();
+                if tys.len() == 1 {
+                    Some(tys[0])
+                } else {
+                    Some(type_args(p, 1, "Cow")?[0])
+                }
+            }
+            _ => Some(type_args(p, 1, "Cow")?[0]),
+        },
+        _ => None,
+    })
+}
+
+/// Returns the length `N` of the array type `[T; N]` when it is an integer literal.
+///
+/// ```
+/// let ty: syn::TypeArray = syn::parse_str("[u8; 32]").unwrap();
+/// assert_eq!(near_syn::array_len(&ty), Some(32));
+/// let ty: syn::TypeArray = syn::parse_str("[u8; N]").unwrap();
+/// assert_eq!(near_syn::array_len(&ty), None);
+/// ```
+pub fn array_len(array: &TypeArray) -> Option<usize> {
+    match &array.len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(len), ..
+        }) => len.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+/// Describes how `serde` represents an `enum` in JSON.
+/// See <https://serde.rs/enum-representations.html>.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerdeEnumRepr {
+    /// Externally tagged, *i.e.*, `{ "Variant": content }`, the default representation.
+    External,
+
+    /// Internally tagged by `#[serde(tag = "...")]`, *i.e.*, `{ "tag": "Variant", ...fields }`.
+    Internal {
+        /// The name of the field holding the variant name.
+        tag: String,
+    },
+
+    /// Adjacently tagged by `#[serde(tag = "...", content = "...")]`,
+    /// *i.e.*, `{ "tag": "Variant", "content": content }`.
+    Adjacent {
+        /// The name of the field holding the variant name.
+        tag: String,
+        /// The name of the field holding the variant content.
+        content: String,
+    },
+
+    /// `#[serde(untagged)]`, *i.e.*, only the variant content.
+    Untagged,
+}
+
+/// Returns the `serde` representation of the `enum` with attributes `attrs`.
+///
+/// ```
+/// use near_syn::{serde_enum_repr, SerdeEnumRepr};
+///
+/// let item: syn::ItemEnum = syn::parse_str("#[serde(tag = \"t\", content = \"c\")] enum E {}").unwrap();
+/// assert_eq!(serde_enum_repr(&item.attrs), SerdeEnumRepr::Adjacent {
+///     tag: "t".to_string(),
+///     content: "c".to_string(),
+/// });
+/// ```
+pub fn serde_enum_repr(attrs: &[Attribute]) -> SerdeEnumRepr {
+    if serde_flag(attrs, "untagged") {
+        return SerdeEnumRepr::Untagged;
+    }
+
+    match (serde_value(attrs, "tag"), serde_value(attrs, "content")) {
+        (Some(tag), Some(content)) => SerdeEnumRepr::Adjacent { tag, content },
+        (Some(tag), None) => SerdeEnumRepr::Internal { tag },
+        _ => SerdeEnumRepr::External,
+    }
+}
+
+/// Returns the string value of the `serde` attribute `name` in `attrs`,
+/// *e.g.*, `"t"` for `#[serde(tag = "t")]`.
+pub fn serde_value(attrs: &[Attribute], name: &str) -> Option<String> {
+    serde_metas(attrs).into_iter().find_map(|meta| match meta {
+        Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(lit),
+            ..
+        }) if path.is_ident(name) => Some(lit.value()),
+        _ => None,
+    })
+}
+
+/// Returns whether the `serde` attribute `name` is present in `attrs`,
+/// either as a flag, *e.g.*, `#[serde(untagged)]`,
+/// or with a value, *e.g.*, `#[serde(default = "path")]`.
+pub fn serde_flag(attrs: &[Attribute], name: &str) -> bool {
+    serde_metas(attrs).iter().any(|meta| match meta {
+        Meta::Path(path) => path.is_ident(name),
+        Meta::NameValue(MetaNameValue { path, .. }) => path.is_ident(name),
+        Meta::List(_) => false,
+    })
+}
+
+/// A named field as seen by `serde`, *i.e.*, after applying its `#[serde(...)]` attributes.
+pub struct SerdeField<'a> {
+    /// The Rust field.
+    pub field: &'a Field,
+
+    /// The name of the field in JSON, after `rename` or `rename_all`.
+    pub name: String,
+
+    /// Whether the field can be missing in JSON,
+    /// *i.e.*, it has `default` or `skip_serializing_if`, or its container has `default`.
+    pub optional: bool,
+
+    /// Whether the field is `flatten`ed into its container.
+    pub flatten: bool,
+}
+
+/// Returns the named `fields` of a `struct` or `enum` variant as seen by `serde`.
+/// `container_attrs` are the attributes of the `struct` or variant declaring `fields`.
+/// Fields marked with `skip` or `skip_serializing` are omitted.
+///
+/// ```
+/// let item: syn::ItemStruct = syn::parse_str(r#"
+///     #[serde(rename_all = "camelCase")]
+///     struct A {
+///         owner_id: AccountId,
+///         #[serde(rename = "total")]
+///         total_supply: U128,
+///         #[serde(skip)]
+///         cache: u32,
+///         #[serde(default)]
+///         memo: String,
+///     }"#).unwrap();
+/// let fields = match &item.fields {
+///     syn::Fields::Named(fields) => near_syn::serde_fields(fields, &item.attrs),
+///     _ => unreachable!(),
+/// };
+/// let fields = fields.iter().map(|f| (f.name.as_str(), f.optional)).collect::<Vec<_>>();
+/// assert_eq!(fields, vec![("ownerId", false), ("total", false), ("memo", true)]);
+/// ```
+pub fn serde_fields<'a>(
+    fields: &'a FieldsNamed,
+    container_attrs: &[Attribute],
+) -> Vec<SerdeField<'a>> {
+    let rename_all = serde_value(container_attrs, "rename_all");
+    let default = serde_flag(container_attrs, "default");
+
+    fields
+        .named
+        .iter()
+        .filter(|field| {
+            !serde_flag(&field.attrs, "skip") && !serde_flag(&field.attrs, "skip_serializing")
+        })
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap().unraw().to_string();
+            let name = match (serde_value(&field.attrs, "rename"), &rename_all) {
+                (Some(name), _) => name,
+                (None, Some(rule)) => rename_field(rule, &ident),
+                (None, None) => ident,
+            };
+            SerdeField {
+                field,
+                name,
+                optional: default
+                    || serde_flag(&field.attrs, "default")
+                    || serde_flag(&field.attrs, "skip_serializing_if"),
+                flatten: serde_flag(&field.attrs, "flatten"),
+            }
+        })
+        .collect()
+}
+
+/// Returns the variants of an `enum` as seen by `serde`, together with their names in JSON.
+/// `enum_attrs` are the attributes of the `enum` declaring `variants`.
+/// Variants marked with `skip` or `skip_serializing` are omitted.
+///
+/// ```
+/// let item: syn::ItemEnum = syn::parse_str(r#"
+///     #[serde(rename_all = "snake_case")]
+///     enum E { NotStarted, #[serde(rename = "done")] Finished, #[serde(skip)] Hidden }"#).unwrap();
+/// let variants = near_syn::serde_variants(&item.variants, &item.attrs)
+///     .into_iter()
+///     .map(|(_, name)| name)
+///     .collect::<Vec<_>>();
+/// assert_eq!(variants, vec!["not_started", "done"]);
+/// ```
+pub fn serde_variants<'a, I: IntoIterator<Item = &'a Variant>>(
+    variants: I,
+    enum_attrs: &[Attribute],
+) -> Vec<(&'a Variant, String)> {
+    let rename_all = serde_value(enum_attrs, "rename_all");
+
+    variants
+        .into_iter()
+        .filter(|variant| {
+            !serde_flag(&variant.attrs, "skip") && !serde_flag(&variant.attrs, "skip_serializing")
+        })
+        .map(|variant| {
+            let ident = variant.ident.unraw().to_string();
+            let name = match (serde_value(&variant.attrs, "rename"), &rename_all) {
+                (Some(name), _) => name,
+                (None, Some(rule)) => rename_variant(rule, &ident),
+                (None, None) => ident,
+            };
+            (variant, name)
+        })
+        .collect()
+}
+
+/// Applies the `rename_all` `rule` to a `snake_case` field `name`, as `serde` does.
+/// Unknown rules leave `name` unchanged.
+fn rename_field(rule: &str, name: &str) -> String {
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => {
+            let mut pascal = String::new();
+            let mut capitalize = true;
+            for ch in name.chars() {
+                if ch == '_' {
+                    capitalize = true;
+                } else if capitalize {
+                    pascal.push(ch.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    pascal.push(ch);
+                }
+            }
+            pascal
+        }
+        "camelCase" => lowercase_first(&rename_field("PascalCase", name)),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Applies the `rename_all` `rule` to a `PascalCase` variant `name`, as `serde` does.
+/// Unknown rules leave `name` unchanged.
+fn rename_variant(rule: &str, name: &str) -> String {
+    match rule {
+        "lowercase" => name.to_ascii_lowercase(),
+        "UPPERCASE" => name.to_ascii_uppercase(),
+        "camelCase" => lowercase_first(name),
+        "snake_case" => {
+            let mut snake = String::new();
+            for (i, ch) in name.char_indices() {
+                if i > 0 && ch.is_uppercase() {
+                    snake.push('_');
+                }
+                snake.push(ch.to_ascii_lowercase());
+            }
+            snake
+        }
+        "SCREAMING_SNAKE_CASE" => rename_variant("snake_case", name).to_ascii_uppercase(),
+        "kebab-case" => rename_variant("snake_case", name).replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => rename_variant("SCREAMING_SNAKE_CASE", name).replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// Returns all the nested metas of the `#[serde(...)]` attributes in `attrs`.
+fn serde_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    let mut metas = Vec::new();
+    for attr in attrs {
+        if attr.path.is_ident("serde") {
+            if let Ok(Meta::List(MetaList { nested, .. })) = attr.parse_meta() {
+                for elem in nested {
+                    if let NestedMeta::Meta(meta) = elem {
+                        metas.push(meta);
+                    }
+                }
+            }
+        }
+    }
+
+    metas
+}
+
+/// Returns the lines of the Rust `doc` comments in `attrs`.
+/// Other `doc` attributes, *e.g.*, `#[doc(hidden)]`, are skipped.
+///
+/// ```
+/// let item: syn::ItemFn = syn::parse_str("/// Line\n#[doc(hidden)]\nfn f() {}").unwrap();
+/// assert_eq!(near_syn::get_docs(&item.attrs), vec![" Line"]);
+/// ```
+pub fn get_docs(attrs: &[Attribute]) -> Vec<String> {
     let mut docs = Vec::new();
     for attr in attrs {
         if attr.path.is_ident("doc") {
@@ -312,8 +1147,6 @@ pub fn get_docs(attrs: &Vec<Attribute>) -> Vec<String> {
             })) = attr.parse_meta()
             {
                 docs.push(lit.value());
-            } else {
-                panic!("not expected");
             }
         }
     }
@@ -321,25 +1154,16 @@ pub fn get_docs(attrs: &Vec<Attribute>) -> Vec<String> {
     docs
 }
 
-/// Writes Rust `doc` comments to `file`.
-/// Each line of `doc` is prefixed with `prefix`.
+/// Writes Rust `doc` comments to `file`, see `get_docs`.
+/// Each line of `doc` is mapped by `mapf`.
 /// See <https://doc.rust-lang.org/rustdoc/the-doc-attribute.html>.
 pub fn write_docs<W: Write, F: Fn(String) -> String>(
     file: &mut W,
-    attrs: &Vec<Attribute>,
+    attrs: &[Attribute],
     mapf: F,
 ) -> io::Result<()> {
-    for attr in attrs {
-        if attr.path.is_ident("doc") {
-            if let Ok(Meta::NameValue(MetaNameValue {
-                lit: Lit::Str(lit), ..
-            })) = attr.parse_meta()
-            {
-                writeln!(file, "{}", mapf(lit.value()))?;
-            } else {
-                panic!("not expected");
-            }
-        }
+    for doc in get_docs(attrs) {
+        writeln!(file, "{}", mapf(doc))?;
     }
 
     Ok(())
@@ -0,0 +1,817 @@
+//! Functions to translate Rust types and items to JSON Schema.
+//!
+//! The translation follows `serde`'s JSON data model,
+//! *i.e.*, the shape of the values the NEAR RPC sends and receives.
+
+use std::{collections::HashMap, mem};
+
+use quote::ToTokens;
+use serde_json::{json, Map, Value};
+use syn::{
+    fold::{self, Fold},
+    parse_quote,
+    visit::{self, Visit},
+    Attribute, Fields, FieldsNamed, GenericArgument, Generics, ImplItemMethod, ItemEnum,
+    ItemStruct, ItemType, Pat, PathArguments, Type, TypePath, Variant,
+};
+
+use crate::{
+    contract::{Contract, ContractItem, NearItem},
+    error::{Error, Location, Result},
+    near_sdk_syn::{
+        array_len, get_docs, is_type, pointee_type, serde_enum_repr, serde_fields, serde_variants,
+        type_args, type_name, NearMethod, SerdeEnumRepr,
+    },
+};
+
+/// The JSON Schema dialect of the documents returned by `schema_root`.
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Regular expression matching valid NEAR account IDs.
+/// See <https://nomicon.io/DataStructures/Account#account-id-rules>.
+pub const ACCOUNT_ID_PATTERN: &str = r"^(([a-z\d]+[-_])*[a-z\d]+\.)*([a-z\d]+[-_])*[a-z\d]+$";
+
+/// Returns a JSON Schema document (draft 2020-12) of the given `contract`.
+/// The `$defs` contain the schema of all the `struct`, `enum` and `type` items in `contract`,
+/// plus the argument object of each method, named `{method}_args`.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     struct Token { owner_id: AccountId }
+///
+///     #[near_bindgen]
+///     impl Contract {
+///         pub fn get(&self, token_id: U64, memo: Option<String>) -> Token {}
+///     }"#).unwrap()).unwrap();
+/// let schema = near_syn::schema::schema_root(&contract).unwrap();
+/// assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+/// assert_eq!(schema["title"], "Contract");
+/// assert_eq!(schema["$defs"]["get_args"], json!({
+///     "type": "object",
+///     "required": ["token_id"],
+///     "properties": {
+///         "token_id": { "type": "string", "pattern": "^[0-9]+$" },
+///         "memo": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+///     },
+/// }));
+/// ```
+pub fn schema_root(contract: &Contract) -> Result<Value> {
+    let mut defs = schema_definitions(contract)?;
+    for name in contract
+        .init_methods
+        .iter()
+        .chain(&contract.view_methods)
+        .chain(&contract.change_methods)
+        .chain(contract.private_callbacks())
+    {
+        if let Some((method, _, file)) = contract.methods.get(name) {
+            let schema = schema_args(method).map_err(|err| err.in_file(file.as_deref()))?;
+            defs.insert(format!("{}_args", name), schema);
+        }
+    }
+
+    let mut root = Map::new();
+    root.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+    if let Some(name) = &contract.name {
+        root.insert("title".to_string(), json!(name));
+    }
+    root.insert("$defs".to_string(), Value::Object(defs));
+
+    let mut root = Value::Object(root);
+    to_draft_2020_12(&mut root);
+    Ok(root)
+}
+
+/// Returns the JSON Schema of the argument object of `method`,
+/// *i.e.*, an object with a property for each argument.
+/// `Option` arguments are not `required`.
+pub fn schema_args(method: &ImplItemMethod) -> Result<Value> {
+    let mut required = Vec::new();
+    let mut properties = Map::new();
+    for pat_type in method.input_args() {
+        if let Pat::Ident(pat_ident) = &*pat_type.pat {
+            let name = pat_ident.ident.to_string();
+            if !is_option(&pat_type.ty) {
+                required.push(name.clone());
+            }
+            properties.insert(name, schema_type(&pat_type.ty)?);
+        }
+    }
+
+    Ok(json!({ "type": "object", "required": required, "properties": properties }))
+}
+
+/// Rewrites the draft-07 `schema` produced by `schema_type` into draft 2020-12.
+/// Draft 2020-12 keeps reusable schemas under `$defs` instead of `definitions`,
+/// so `$ref`s are rewritten accordingly.
+/// Besides, tuples are given by `prefixItems` instead of an array of `items`,
+/// where `items` must be a single schema.
+fn to_draft_2020_12(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if let Some(Value::Array(_)) = object.get("items") {
+                *object = mem::take(object)
+                    .into_iter()
+                    .flat_map(|(key, value)| match (key.as_str(), value) {
+                        ("items", Value::Array(items)) => vec![
+                            ("prefixItems".to_string(), Value::Array(items)),
+                            ("items".to_string(), json!(false)),
+                        ],
+                        (_, value) => vec![(key, value)],
+                    })
+                    .collect();
+            }
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(r) if key == "$ref" => {
+                        if let Some(name) = r.strip_prefix("#/definitions/") {
+                            *r = format!("#/$defs/{}", name);
+                        }
+                    }
+                    value => to_draft_2020_12(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(to_draft_2020_12),
+        _ => {}
+    }
+}
+
+/// Returns the doc-comments in `attrs` as a single string suitable for a
+/// JSON Schema `description`.
+/// Each line is trimmed and lines are joined by `\n`.
+/// Returns `None` when there are no doc-comments.
+///
+/// ```
+/// let item: syn::ItemType = syn::parse_str("/// Line 1\n///   Line 2\ntype T = u64;").unwrap();
+/// assert_eq!(near_syn::schema::schema_description(&item.attrs), Some("Line 1\nLine 2".to_string()));
+/// ```
+pub fn schema_description(attrs: &[Attribute]) -> Option<String> {
+    let docs = get_docs(attrs);
+    if docs.is_empty() {
+        None
+    } else {
+        Some(
+            docs.iter()
+                .map(|line| line.trim())
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        )
+    }
+}
+
+/// Returns the JSON Schema definitions of all the `struct`, `enum` and `type` items in `contract`.
+/// The definitions are keyed by the item name.
+/// Types referenced by other schemas point to these definitions using `$ref`.
+///
+/// The type parameters of generic items accept any JSON value,
+/// *i.e.*, they are translated as `serde_json::Value`.
+/// Besides, each instantiation of a generic item used by the contract has its own definition,
+/// named as in `schemars`, *e.g.*, `Page_for_Token` for `Page<Token>`, see `schema_type`.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     struct Page<T> { items: Vec<T> }
+///
+///     #[near_bindgen]
+///     impl Contract {
+///         pub fn tokens(&self) -> Page<Token> {}
+///     }"#).unwrap()).unwrap();
+/// let definitions = near_syn::schema::schema_definitions(&contract).unwrap();
+/// assert_eq!(definitions["Page"]["properties"]["items"], serde_json::json!({ "type": "array", "items": {} }));
+/// assert_eq!(definitions["Page_for_Token"]["properties"]["items"], serde_json::json!({
+///     "type": "array",
+///     "items": { "$ref": "#/definitions/Token" },
+/// }));
+/// ```
+pub fn schema_definitions(contract: &Contract) -> Result<Map<String, Value>> {
+    let generic_items = contract
+        .items
+        .iter()
+        .filter_map(|item| {
+            let ident = item.ident()?;
+            let generics = item_generics(item)?;
+            if generics.type_params().next().is_some() {
+                Some((ident.to_string(), item))
+            } else {
+                None
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    let mut instances = Instances {
+        generic_items: &generic_items,
+        found: Vec::new(),
+    };
+
+    let mut definitions = Map::new();
+    for item in &contract.items {
+        let (name, item_any) = match (item.ident(), bind_item(item, &[])) {
+            (Some(ident), Some(item_any)) => (ident.to_string(), item_any),
+            _ => continue,
+        };
+        let schema = schema_item(&item_any).map_err(|err| err.in_file(item.file.as_deref()))?;
+        definitions.insert(name, schema);
+        instances.visit_near_item(&item_any);
+    }
+    for (method, _, _) in contract.methods.values() {
+        instances.visit_signature(&method.sig);
+    }
+
+    while let Some((name, item, args)) = instances.found.pop() {
+        if definitions.contains_key(&name) {
+            continue;
+        }
+        if let Some(instance) = bind_item(item, &args) {
+            let schema = schema_item(&instance).map_err(|err| err.in_file(item.file.as_deref()))?;
+            definitions.insert(name, schema);
+            instances.visit_near_item(&instance);
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// Returns the generics of `item`, unless it is an `impl`.
+fn item_generics(item: &NearItem) -> Option<&Generics> {
+    match item {
+        NearItem::Struct(item_struct) => Some(&item_struct.generics),
+        NearItem::Enum(item_enum) => Some(&item_enum.generics),
+        NearItem::Type(item_type) => Some(&item_type.generics),
+        NearItem::Impl(_) => None,
+    }
+}
+
+/// Returns `item` with its type parameters replaced by `args`, see `TypeParams::bind`.
+/// Returns `None` for `impl`s.
+fn bind_item(item: &NearItem, args: &[Type]) -> Option<NearItem> {
+    let mut params = TypeParams::bind(item_generics(item)?, args);
+    Some(match item {
+        NearItem::Struct(item_struct) => {
+            NearItem::Struct(params.fold_item_struct(item_struct.clone()))
+        }
+        NearItem::Enum(item_enum) => NearItem::Enum(params.fold_item_enum(item_enum.clone())),
+        NearItem::Type(item_type) => NearItem::Type(params.fold_item_type(item_type.clone())),
+        NearItem::Impl(_) => return None,
+    })
+}
+
+/// Returns the JSON Schema of the `struct`, `enum` or `type` `item`.
+fn schema_item(item: &NearItem) -> Result<Value> {
+    match item {
+        NearItem::Struct(item_struct) => schema_struct(item_struct),
+        NearItem::Enum(item_enum) => schema_enum(item_enum),
+        NearItem::Type(item_type) => schema_typedef(item_type),
+        NearItem::Impl(_) => Ok(json!({})),
+    }
+}
+
+/// Collects the instantiations of `generic_items` found in the visited types,
+/// along with their definition name and type arguments.
+struct Instances<'a> {
+    generic_items: &'a HashMap<String, &'a ContractItem>,
+    found: Vec<(String, &'a ContractItem, Vec<Type>)>,
+}
+
+impl<'a> Instances<'a> {
+    fn visit_near_item(&mut self, item: &NearItem) {
+        match item {
+            NearItem::Struct(item_struct) => self.visit_item_struct(item_struct),
+            NearItem::Enum(item_enum) => self.visit_item_enum(item_enum),
+            NearItem::Type(item_type) => self.visit_item_type(item_type),
+            NearItem::Impl(_) => {}
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Instances<'a> {
+    fn visit_type_path(&mut self, p: &'ast TypePath) {
+        visit::visit_type_path(self, p);
+
+        let item = match self.generic_items.get(&type_name(p)) {
+            Some(item) => *item,
+            None => return,
+        };
+        if let (Ok(name), PathArguments::AngleBracketed(args)) = (
+            schema_def_name(p),
+            &p.path.segments.last().unwrap().arguments,
+        ) {
+            let args = args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                })
+                .collect();
+            self.found.push((name, item, args));
+        }
+    }
+}
+
+/// Replaces the type parameters of a generic item by their arguments.
+struct TypeParams(HashMap<String, Type>);
+
+impl TypeParams {
+    /// Binds the type parameters declared in `generics` to `args`, in order.
+    /// Type parameters with no argument are bound to `serde_json::Value`, *i.e.*, any JSON value.
+    fn bind(generics: &Generics, args: &[Type]) -> Self {
+        let any: Type = parse_quote!(serde_json::Value);
+        TypeParams(
+            generics
+                .type_params()
+                .enumerate()
+                .map(|(i, param)| {
+                    let arg = args.get(i).cloned().unwrap_or_else(|| any.clone());
+                    (param.ident.to_string(), arg)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Fold for TypeParams {
+    fn fold_type(&mut self, ty: Type) -> Type {
+        if let Type::Path(p) = &ty {
+            let arg = p
+                .path
+                .get_ident()
+                .filter(|_| p.qself.is_none())
+                .and_then(|ident| self.0.get(&ident.to_string()));
+            if let Some(arg) = arg {
+                return arg.clone();
+            }
+        }
+
+        fold::fold_type(self, ty)
+    }
+}
+
+/// Returns the JSON Schema of the given `struct`.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let schema = near_syn::schema::schema_struct(&syn::parse_str(r#"
+///     /// Doc-comments are translated.
+///     #[derive(Serialize)]
+///     struct A {
+///         field: u32,
+///         optional: Option<String>,
+///     }"#).unwrap()).unwrap();
+/// assert_eq!(schema, json!({
+///     "description": "Doc-comments are translated.",
+///     "type": "object",
+///     "required": ["field"],
+///     "properties": {
+///         "field": { "type": "integer", "format": "uint32", "minimum": 0 },
+///         "optional": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+///     },
+/// }));
+/// ```
+pub fn schema_struct(item_struct: &ItemStruct) -> Result<Value> {
+    Ok(with_description(
+        schema_fields(&item_struct.fields, &item_struct.attrs)?,
+        &item_struct.attrs,
+    ))
+}
+
+/// Returns the JSON Schema of the given `enum`.
+///
+/// `enum`s with only unit variants are translated to a `string` enumeration.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let schema = near_syn::schema::schema_enum(&syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     enum E { V1, V2 }"#).unwrap()).unwrap();
+/// assert_eq!(schema, json!({ "type": "string", "enum": ["V1", "V2"] }));
+/// ```
+///
+/// Otherwise, each variant is translated according to `serde`'s enum representation,
+/// externally tagged by default.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let schema = near_syn::schema::schema_enum(&syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     enum E { V1, V2(u8) }"#).unwrap()).unwrap();
+/// assert_eq!(schema, json!({ "oneOf": [
+///     { "type": "string", "enum": ["V1"] },
+///     {
+///         "type": "object",
+///         "required": ["V2"],
+///         "properties": { "V2": { "type": "integer", "format": "uint8", "minimum": 0 } },
+///         "additionalProperties": false,
+///     },
+/// ]}));
+/// ```
+pub fn schema_enum(item_enum: &ItemEnum) -> Result<Value> {
+    let repr = serde_enum_repr(&item_enum.attrs);
+    let variants = serde_variants(&item_enum.variants, &item_enum.attrs);
+    let schema = if repr == SerdeEnumRepr::External
+        && variants
+            .iter()
+            .all(|(variant, _)| variant.fields == Fields::Unit)
+    {
+        let names = variants
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect::<Vec<String>>();
+        json!({ "type": "string", "enum": names })
+    } else {
+        let mut schemas = Vec::new();
+        for (variant, name) in variants {
+            schemas.push(with_description(
+                schema_variant(variant, name, &repr)?,
+                &variant.attrs,
+            ));
+        }
+        json!({ "oneOf": schemas })
+    };
+
+    Ok(with_description(schema, &item_enum.attrs))
+}
+
+/// Returns the JSON Schema of an `enum` `variant` named `name` in JSON
+/// according to the `serde` representation `repr`.
+fn schema_variant(variant: &Variant, name: String, repr: &SerdeEnumRepr) -> Result<Value> {
+    let tag_schema = json!({ "type": "string", "enum": [name.clone()] });
+    let tag_object = |tag: &String| {
+        json!({
+            "type": "object",
+            "required": [tag],
+            "properties": { tag: tag_schema.clone() },
+        })
+    };
+    Ok(match repr {
+        SerdeEnumRepr::External => match &variant.fields {
+            Fields::Unit => tag_schema,
+            fields => json!({
+                "type": "object",
+                "required": [name.clone()],
+                "properties": { name: schema_fields(fields, &variant.attrs)? },
+                "additionalProperties": false,
+            }),
+        },
+        SerdeEnumRepr::Internal { tag } => match &variant.fields {
+            Fields::Unit => tag_object(tag),
+            Fields::Named(fields) => {
+                let mut properties = Map::new();
+                properties.insert(tag.clone(), tag_schema.clone());
+                schema_named_fields(fields, &variant.attrs, vec![tag.clone()], properties)?
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                json!({ "allOf": [tag_object(tag), schema_type(&fields.unnamed[0].ty)?] })
+            }
+            Fields::Unnamed(_) => {
+                return Err(Error::TupleVariant {
+                    name,
+                    location: Location::of(variant),
+                })
+            }
+        },
+        SerdeEnumRepr::Adjacent { tag, content } => match &variant.fields {
+            Fields::Unit => tag_object(tag),
+            fields => json!({
+                "type": "object",
+                "required": [tag, content],
+                "properties": { tag: tag_schema, content: schema_fields(fields, &variant.attrs)? },
+            }),
+        },
+        SerdeEnumRepr::Untagged => schema_fields(&variant.fields, &variant.attrs)?,
+    })
+}
+
+/// Returns the JSON Schema of the given type alias, *i.e.*, the schema of the aliased type.
+///
+/// ```
+/// use serde_json::json;
+///
+/// let schema = near_syn::schema::schema_typedef(&syn::parse_str("type T = Vec<U128>;").unwrap()).unwrap();
+/// assert_eq!(schema, json!({ "type": "array", "items": { "type": "string", "pattern": "^[0-9]+$" } }));
+/// ```
+pub fn schema_typedef(item_type: &ItemType) -> Result<Value> {
+    Ok(with_description(
+        schema_type(&item_type.ty)?,
+        &item_type.attrs,
+    ))
+}
+
+/// Return the JSON Schema of the Rust type represented by `ty`.
+///
+/// ### Examples
+///
+/// Rust primitives types and `String` are included.
+/// Integers carry their `format` as in `schemars`.
+///
+/// ```
+/// use serde_json::json;
+/// use syn::parse_str;
+/// use near_syn::schema::schema_type;
+///
+/// assert_eq!(schema_type(&parse_str("bool").unwrap()).unwrap(), json!({ "type": "boolean" }));
+/// assert_eq!(schema_type(&parse_str("i64").unwrap()).unwrap(), json!({ "type": "integer", "format": "int64" }));
+/// assert_eq!(schema_type(&parse_str("u128").unwrap()).unwrap(), json!({ "type": "integer", "format": "uint128", "minimum": 0 }));
+/// assert_eq!(schema_type(&parse_str("f64").unwrap()).unwrap(), json!({ "type": "number", "format": "double" }));
+/// assert_eq!(schema_type(&parse_str("serde_json::Value").unwrap()).unwrap(), json!({}));
+/// assert_eq!(schema_type(&parse_str("String").unwrap()).unwrap(), json!({ "type": "string" }));
+/// ```
+///
+/// NEAR SDK JSON types are encoded as `string`s.
+/// Integer types carry a `pattern` matching their decimal digits,
+/// and account IDs carry the pattern and length of valid account IDs.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::schema::schema_type;
+/// assert_eq!(
+///     schema_type(&parse_str("U128").unwrap()).unwrap(),
+///     json!({ "type": "string", "pattern": "^[0-9]+$" }),
+/// );
+/// assert_eq!(
+///     schema_type(&parse_str("AccountId").unwrap()).unwrap(),
+///     json!({
+///         "type": "string",
+///         "pattern": "^(([a-z\\d]+[-_])*[a-z\\d]+\\.)*([a-z\\d]+[-_])*[a-z\\d]+$",
+///         "minLength": 2,
+///         "maxLength": 64,
+///     }),
+/// );
+/// ```
+///
+/// The other near-sdk types exported by `near_syn::ts::ts_prelude` are translated inline
+/// as in the default `SdkVersion`, so they need no definition.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::schema::schema_type;
+/// assert_eq!(
+///     schema_type(&parse_str("NearToken").unwrap()).unwrap(),
+///     json!({ "type": "string", "pattern": "^[0-9]+$" }),
+/// );
+/// assert_eq!(
+///     schema_type(&parse_str("Timestamp").unwrap()).unwrap(),
+///     json!({ "type": "integer", "format": "uint64", "minimum": 0 }),
+/// );
+/// ```
+///
+/// Rust standard and collections types are included in the translation.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::schema::schema_type;
+/// assert_eq!(
+///     schema_type(&parse_str("HashMap<AccountId, Vec<U64>>").unwrap()).unwrap(),
+///     json!({ "type": "object", "additionalProperties": { "type": "array", "items": { "type": "string", "pattern": "^[0-9]+$" } } }),
+/// );
+/// assert_eq!(
+///     schema_type(&parse_str("(bool, String)").unwrap()).unwrap(),
+///     json!({ "type": "array", "items": [{ "type": "boolean" }, { "type": "string" }], "minItems": 2, "maxItems": 2 }),
+/// );
+/// ```
+///
+/// Any other type is assumed to be defined by the contract,
+/// and thus it is referenced from the definitions.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::schema::schema_type;
+/// assert_eq!(schema_type(&parse_str("Token").unwrap()).unwrap(), json!({ "$ref": "#/definitions/Token" }));
+/// ```
+///
+/// Instantiations of generic types are referenced by their own definition, see `schema_definitions`.
+///
+/// ```
+/// # use serde_json::json;
+/// # use syn::parse_str;
+/// # use near_syn::schema::schema_type;
+/// assert_eq!(
+///     schema_type(&parse_str("Page<Token>").unwrap()).unwrap(),
+///     json!({ "$ref": "#/definitions/Page_for_Token" }),
+/// );
+/// assert_eq!(
+///     schema_type(&parse_str("Pair<U64, Vec<bool>>").unwrap()).unwrap(),
+///     json!({ "$ref": "#/definitions/Pair_for_U64_and_Vec_for_bool" }),
+/// );
+/// ```
+///
+/// ## Errors
+///
+/// Returns an error on the same situations as `near_syn::ts::ts_type`.
+pub fn schema_type(ty: &Type) -> Result<Value> {
+    Ok(match ty {
+        Type::Path(p) => match type_name(p).as_str() {
+            "bool" => json!({ "type": "boolean" }),
+            int @ ("u8" | "u16" | "u32" | "u64" | "u128" | "usize") => {
+                json!({ "type": "integer", "format": format!("uint{}", &int[1..]), "minimum": 0 })
+            }
+            int @ ("i8" | "i16" | "i32" | "i64" | "i128" | "isize") => {
+                json!({ "type": "integer", "format": format!("int{}", &int[1..]) })
+            }
+            "f32" => json!({ "type": "number", "format": "float" }),
+            "f64" => json!({ "type": "number", "format": "double" }),
+            "String" | "str" => json!({ "type": "string" }),
+            "char" => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+            "serde_json::Value" => json!({}),
+            "U64" | "U128" | "Balance" | "Gas" | "NearToken" => {
+                json!({ "type": "string", "pattern": "^[0-9]+$" })
+            }
+            "I64" | "I128" => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+            "Timestamp" | "Duration" | "StorageUsage" => {
+                json!({ "type": "integer", "format": "uint64", "minimum": 0 })
+            }
+            "CryptoHash" => json!({
+                "type": "array",
+                "items": { "type": "integer", "format": "uint8", "minimum": 0 },
+                "minItems": 32,
+                "maxItems": 32,
+            }),
+            "PublicKey" | "Base58PublicKey" | "Base58CryptoHash" => json!({ "type": "string" }),
+            "Base64VecU8" => json!({ "type": "string", "contentEncoding": "base64" }),
+            "AccountId" | "ValidAccountId" => json!({
+                "type": "string",
+                "pattern": ACCOUNT_ID_PATTERN,
+                "minLength": 2,
+                "maxLength": 64,
+            }),
+            "Option" => {
+                let targs = type_args(p, 1, "Option")?;
+                json!({ "anyOf": [schema_type(targs[0])?, { "type": "null" }] })
+            }
+            "Vec" | "VecDeque" | "LinkedList" => {
+                let targs = type_args(p, 1, "Vec")?;
+                json!({ "type": "array", "items": schema_type(targs[0])? })
+            }
+            "HashSet" | "BTreeSet" | "IndexSet" => {
+                let targs = type_args(p, 1, "Vec")?;
+                json!({ "type": "array", "items": schema_type(targs[0])?, "uniqueItems": true })
+            }
+            "HashMap" | "BTreeMap" | "IndexMap" => {
+                let targs = type_args(p, 2, "HashMap")?;
+                json!({ "type": "object", "additionalProperties": schema_type(targs[1])? })
+            }
+            "Result" => {
+                let targs = type_args(p, 2, "Result")?;
+                json!({ "oneOf": [
+                    {
+                        "type": "object",
+                        "required": ["Ok"],
+                        "properties": { "Ok": schema_type(targs[0])? },
+                        "additionalProperties": false,
+                    },
+                    {
+                        "type": "object",
+                        "required": ["Err"],
+                        "properties": { "Err": schema_type(targs[1])? },
+                        "additionalProperties": false,
+                    },
+                ]})
+            }
+            "Box" | "Rc" | "Arc" | "Cow" => schema_type(pointee_type(p)?.unwrap())?,
+            _ => json!({ "$ref": format!("#/definitions/{}", schema_def_name(p)?) }),
+        },
+        Type::Paren(paren) => schema_type(&paren.elem)?,
+        Type::Tuple(tuple) => {
+            if tuple.elems.is_empty() {
+                json!({ "type": "null" })
+            } else {
+                let items = tuple
+                    .elems
+                    .iter()
+                    .map(schema_type)
+                    .collect::<Result<Vec<Value>>>()?;
+                json!({
+                    "type": "array",
+                    "items": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                })
+            }
+        }
+        Type::Array(array) => {
+            let items = schema_type(&array.elem)?;
+            match array_len(array) {
+                Some(len) => json!({
+                    "type": "array",
+                    "items": vec![items; len],
+                    "minItems": len,
+                    "maxItems": len,
+                }),
+                None => json!({ "type": "array", "items": items }),
+            }
+        }
+        Type::Slice(slice) => json!({ "type": "array", "items": schema_type(&slice.elem)? }),
+        Type::Reference(reference) => schema_type(&reference.elem)?,
+        _ => return Err(Error::unsupported_type(ty)),
+    })
+}
+
+/// Returns the name of the definition of the type given by `p`, as named by `schemars`.
+/// Generic type arguments are appended to the type name,
+/// *e.g.*, `Pair_for_U64_and_Vec_for_bool` for `Pair<U64, Vec<bool>>`.
+fn schema_def_name(p: &TypePath) -> Result<String> {
+    let name = type_name(p);
+    let mut name = name.rsplit("::").next().unwrap_or(&name).to_string();
+    if let PathArguments::AngleBracketed(args) = &p.path.segments.last().unwrap().arguments {
+        let mut args_names = Vec::new();
+        for arg in &args.args {
+            if let GenericArgument::Type(ty) = arg {
+                args_names.push(match ty {
+                    Type::Path(p) => schema_def_name(p)?,
+                    ty => ty.to_token_stream().to_string().replace(' ', ""),
+                });
+            }
+        }
+        if !args_names.is_empty() {
+            name = format!("{}_for_{}", name, args_names.join("_and_"));
+        }
+    }
+
+    Ok(name)
+}
+
+/// Returns the JSON Schema of the `fields` of a `struct` or `enum` variant.
+fn schema_fields(fields: &Fields, attrs: &[Attribute]) -> Result<Value> {
+    Ok(match fields {
+        Fields::Named(fields) => schema_named_fields(fields, attrs, Vec::new(), Map::new())?,
+        Fields::Unnamed(fields) => {
+            let mut items = fields
+                .unnamed
+                .iter()
+                .map(|field| schema_type(&field.ty))
+                .collect::<Result<Vec<Value>>>()?;
+            if items.len() == 1 {
+                items.remove(0)
+            } else {
+                json!({
+                    "type": "array",
+                    "items": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                })
+            }
+        }
+        Fields::Unit => json!({ "type": "null" }),
+    })
+}
+
+/// Returns the JSON Schema object of the named `fields` declared in a container with `attrs`,
+/// appended to the given `required` and `properties`.
+/// Flattened fields are combined with the object using `allOf`.
+fn schema_named_fields(
+    fields: &FieldsNamed,
+    attrs: &[Attribute],
+    mut required: Vec<String>,
+    mut properties: Map<String, Value>,
+) -> Result<Value> {
+    let mut flattened = Vec::new();
+    for field in serde_fields(fields, attrs) {
+        let schema = schema_type(&field.field.ty)?;
+        if field.flatten {
+            flattened.push(schema);
+            continue;
+        }
+        if !field.optional && !is_option(&field.field.ty) {
+            required.push(field.name.clone());
+        }
+        properties.insert(field.name, with_description(schema, &field.field.attrs));
+    }
+
+    let object = json!({ "type": "object", "required": required, "properties": properties });
+    if flattened.is_empty() {
+        Ok(object)
+    } else {
+        flattened.insert(0, object);
+        Ok(json!({ "allOf": flattened }))
+    }
+}
+
+/// `serde` allows `Option` fields to be missing, so they are not `required`.
+fn is_option(ty: &Type) -> bool {
+    is_type(ty, "Option")
+}
+
+/// Prepends the `description` taken from the doc-comments in `attrs` to `schema`, if any.
+fn with_description(schema: Value, attrs: &[Attribute]) -> Value {
+    match (schema_description(attrs), schema) {
+        (Some(description), Value::Object(fields)) => {
+            let mut schema = Map::new();
+            schema.insert("description".to_string(), Value::String(description));
+            schema.extend(fields);
+            Value::Object(schema)
+        }
+        (_, schema) => schema,
+    }
+}
@@ -1,26 +1,101 @@
 //! Functions to transpile Rust to TypeScript.
 
 use crate::{
-    contract::{Contract, NearItem},
-    near_sdk_syn::{join_path, write_docs, NearImpl, NearMethod, NearSerde},
+    contract::{Contract, NearItem, NearItemTrait},
+    error::{Error, Location, Result},
+    near_sdk_syn::{
+        array_len, is_type, pointee_type, result_types, serde_enum_repr, serde_fields,
+        serde_variants, type_args, type_name, write_docs, NearExtContract, NearImpl, NearMethod,
+        NearSerde, SerdeEnumRepr, SerdeField,
+    },
 };
 use std::{
     io::{self, Write},
     ops::Deref,
+    str::FromStr,
 };
 use syn::{
-    Attribute, Fields, ImplItemMethod, ItemEnum, ItemImpl, ItemStruct, PathArguments, ReturnType,
-    Type,
+    Attribute, Fields, FieldsNamed, GenericArgument, Generics, ImplItemMethod, ItemEnum, ItemImpl,
+    ItemStruct, PathArguments, ReturnType, Type, Variant,
 };
 
-/// Exports common NEAR Rust SDK types based on
-/// <https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/>.
+/// Options to customize the emitted TypeScript bindings.
+#[derive(Debug, Clone, Default)]
+pub struct TsOptions {
+    /// Emits a `connect{Contract}` factory wrapping the `near-api-js` `Contract`,
+    /// see `ts_contract_factory`.
+    pub factory: bool,
+
+    /// Emits a `FunctionCall` action builder for each change method,
+    /// see `ts_contract_actions`.
+    pub actions: bool,
+
+    /// Imports `zod` to validate values at runtime,
+    /// see the `zod` module.
+    pub zod: bool,
+
+    /// Version of the NEAR Rust SDK whose types are exported by `ts_prelude`.
+    pub sdk: SdkVersion,
+
+    /// Module to import the NEAR Rust SDK types from, instead of declaring them,
+    /// so that the bindings of several contracts can share them.
+    pub prelude_module: Option<String>,
+
+    /// TypeScript types to declare instead of the translated ones, by name,
+    /// *e.g.*, `("Timestamp", "bigint")` declares `export type Timestamp = bigint;`,
+    /// see `ts_type_overrides`.
+    pub types: Vec<(String, String)>,
+}
+
+/// Major versions of the NEAR Rust SDK supported by `ts_prelude`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SdkVersion {
+    /// `near-sdk` 3.x, where `Gas` is a raw `u64` and account IDs are validated by `ValidAccountId`.
+    V3,
+
+    /// `near-sdk` 4.x.
+    #[default]
+    V4,
+
+    /// `near-sdk` 5.x, where `NearToken` replaces `Balance`.
+    V5,
+}
+
+impl SdkVersion {
+    /// Returns the TypeScript declarations of the types of this version of the SDK.
+    pub fn prelude(&self) -> &'static str {
+        match self {
+            SdkVersion::V3 => include_str!("_prelude_sdk3.ts"),
+            SdkVersion::V4 => include_str!("_prelude_sdk4.ts"),
+            SdkVersion::V5 => include_str!("_prelude_sdk5.ts"),
+        }
+    }
+}
+
+impl FromStr for SdkVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "3" | "3.x" => Ok(SdkVersion::V3),
+            "4" | "4.x" => Ok(SdkVersion::V4),
+            "5" | "5.x" => Ok(SdkVersion::V5),
+            _ => Err(format!(
+                "unsupported near-sdk version `{}`, expected 3, 4 or 5",
+                s
+            )),
+        }
+    }
+}
+
+/// Exports common NEAR Rust SDK types based on the `options.sdk` version,
+/// by default <https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/>.
 /// Moreover, it adds a header indicating the time and binary that
 /// generated these bindings.
 ///
 /// ```
 /// let mut buf = Vec::new();
-/// near_syn::ts::ts_prelude(&mut buf, " 2021".to_string(), "bin");
+/// near_syn::ts::ts_prelude(&mut buf, " 2021".to_string(), "bin", &Default::default());
 /// assert_eq!(String::from_utf8_lossy(&buf), format!(
 /// r#"// TypeScript bindings automatically generated by bin v{} {} 2021, DO NOT MODIFY!
 ///
@@ -74,12 +149,260 @@ use syn::{
 ///  */
 /// export type ValidAccountId = string;
 ///
+/// /**
+///  * Represents the amount of gas, encoded as a `string`.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/struct.Gas.html.
+///  */
+/// export type Gas = string;
+///
+/// /**
+///  * Public key encoded as a `string`, prefixed by its curve, _e.g._, `ed25519:`.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/struct.PublicKey.html.
+///  */
+/// export type PublicKey = string;
+///
+/// /**
+///  * Timestamp in nanoseconds.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.Timestamp.html.
+///  */
+/// export type Timestamp = RawU64;
+///
+/// /**
+///  * Duration in nanoseconds.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.Duration.html.
+///  */
+/// export type Duration = RawU64;
+///
+/// /**
+///  * Storage usage in bytes.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.StorageUsage.html.
+///  */
+/// export type StorageUsage = RawU64;
+///
+/// /**
+///  * Hash of 32 bytes.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.CryptoHash.html.
+///  */
+/// export type CryptoHash = number[];
+///
+/// /**
+///  * Hash of 32 bytes encoded in base58 into a `string`.
+///  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/type.Base58CryptoHash.html.
+///  */
+/// export type Base58CryptoHash = string;
+///
+/// /**
+///  * Raw `u64` integer serialized as a JSON `number`.
+///  * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U64`.
+///  */
+/// export type RawU64 = number;
+///
+/// /**
+///  * Raw `i64` integer serialized as a JSON `number`.
+///  * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I64`.
+///  */
+/// export type RawI64 = number;
+///
+/// /**
+///  * Raw `u128` integer serialized as a JSON `number`.
+///  * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U128`.
+///  */
+/// export type RawU128 = number;
+///
+/// /**
+///  * Raw `i128` integer serialized as a JSON `number`.
+///  * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I128`.
+///  */
+/// export type RawI128 = number;
+///
+/// /**
+///  * Options of a call to a change method.
+///  * A deposit can only be attached to payable methods, see `PayableCallOptions`.
+///  */
+/// export type CallOptions = {{
+///     /**
+///      * Amount of gas to attach to the call, in gas units.
+///      */
+///     gas?: string | bigint;
+///
+///     /**
+///      * Metadata passed to the wallet when the call is signed through it.
+///      */
+///     walletMeta?: string;
+///
+///     /**
+///      * URL the wallet redirects to after the call is signed through it.
+///      */
+///     walletCallbackUrl?: string;
+///
+///     attachedDeposit?: never;
+/// }};
+///
+/// /**
+///  * Options of a call to a payable change method.
+///  */
+/// export type PayableCallOptions = Omit<CallOptions, "attachedDeposit"> & {{
+///     /**
+///      * Amount of NEAR to attach to the call, in yoctoNEAR.
+///      */
+///     attachedDeposit?: string;
+/// }};
+///
 /// "#,
 ///   env!("CARGO_PKG_VERSION"),
 ///   env!("CARGO_PKG_REPOSITORY"),
 ///   ));
 /// ```
-pub fn ts_prelude<W: Write>(buf: &mut W, now: String, bin_name: &str) -> io::Result<()> {
+///
+/// When `options.factory` is set, the types used by `ts_contract_factory` are imported first.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let options = near_syn::ts::TsOptions { factory: true, ..Default::default() };
+/// near_syn::ts::ts_prelude(&mut buf, "".to_string(), "bin", &options);
+/// assert!(String::from_utf8_lossy(&buf).contains(r#", DO NOT MODIFY!
+///
+/// import BN from "bn.js";
+/// import { Account, Contract as NearContract } from "near-api-js";
+///
+/// // Exports common NEAR Rust SDK types"#));
+/// ```
+///
+/// When `options.prelude_module` is set, the SDK types are imported from that module instead,
+/// so that the bindings of several contracts do not redeclare them.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let options = near_syn::ts::TsOptions {
+///     sdk: near_syn::ts::SdkVersion::V5,
+///     prelude_module: Some("./near-sdk".to_string()),
+///     ..Default::default()
+/// };
+/// near_syn::ts::ts_prelude(&mut buf, "".to_string(), "bin", &options);
+/// assert!(String::from_utf8_lossy(&buf).ends_with(r#", DO NOT MODIFY!
+///
+/// import type { U64, I64, U128, I128, Base64VecU8, NearToken, Balance, AccountId, Gas, PublicKey, Timestamp, Duration, StorageUsage, CryptoHash, Base58CryptoHash, RawU64, RawI64, RawU128, RawI128, CallOptions, PayableCallOptions } from "./near-sdk";
+///
+/// "#));
+/// ```
+pub fn ts_prelude<W: Write>(
+    buf: &mut W,
+    now: String,
+    bin_name: &str,
+    options: &TsOptions,
+) -> io::Result<()> {
+    ts_header(buf, now, bin_name)?;
+
+    let mut imports = Vec::new();
+    if options.factory {
+        imports.push("import BN from \"bn.js\";".to_string());
+        imports
+            .push("import { Account, Contract as NearContract } from \"near-api-js\";".to_string());
+    }
+    if options.zod {
+        imports.push("import { z } from \"zod\";".to_string());
+    }
+    if let Some(module) = &options.prelude_module {
+        let names = ts_prelude_types(options.sdk)
+            .into_iter()
+            .filter(|name| !options.types.iter().any(|(ty, _)| ty == name))
+            .collect::<Vec<_>>();
+        imports.push(format!(
+            "import type {{ {} }} from {:?};",
+            names.join(", "),
+            module
+        ));
+    }
+    for import in &imports {
+        writeln!(buf, "{}", import)?;
+    }
+    if !imports.is_empty() {
+        writeln!(buf)?;
+    }
+
+    if options.prelude_module.is_none() {
+        let prelude = [options.sdk.prelude(), include_str!("_prelude.ts")];
+        for src in prelude {
+            writeln!(buf, "{}", ts_override_decls(src, &options.types))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the declarations of the types overridden in `types` within the TypeScript `src`.
+fn ts_override_decls(src: &str, types: &[(String, String)]) -> String {
+    let mut lines = Vec::new();
+    let mut skip_until_end = false;
+    for line in src.lines() {
+        if skip_until_end {
+            skip_until_end = !line.starts_with('}');
+            continue;
+        }
+
+        let name = line
+            .strip_prefix("export type ")
+            .and_then(|decl| decl.split(' ').next());
+        match types.iter().find(|(ty, _)| Some(ty.as_str()) == name) {
+            Some((name, ts)) => {
+                lines.push(format!("export type {} = {};", name, ts));
+                skip_until_end = !line.ends_with(';');
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    let mut decls = lines.join("\n");
+    if src.ends_with('\n') {
+        decls.push('\n');
+    }
+    decls
+}
+
+/// Declares the types overridden in `options.types`,
+/// except those declared by the prelude, which are overridden by `ts_prelude` instead.
+/// When the prelude is imported from `options.prelude_module`, overridden prelude types are not imported but declared here.
+/// The items of the contract with the same name should not be emitted.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let options = near_syn::ts::TsOptions {
+///     types: vec![
+///         ("Timestamp".to_string(), "bigint".to_string()),
+///         ("Metadata".to_string(), "Record<string, unknown>".to_string()),
+///     ],
+///     ..Default::default()
+/// };
+/// near_syn::ts::ts_type_overrides(&mut buf, &options).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), "export type Metadata = Record<string, unknown>;\n\n");
+///
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_prelude(&mut buf, "".to_string(), "bin", &options).unwrap();
+/// assert!(String::from_utf8_lossy(&buf).contains("\nexport type Timestamp = bigint;\n"));
+/// ```
+pub fn ts_type_overrides<W: Write>(buf: &mut W, options: &TsOptions) -> io::Result<()> {
+    let prelude_types = ts_prelude_types(options.sdk);
+    for (name, ts) in &options.types {
+        if options.prelude_module.is_some() || !prelude_types.contains(&name.as_str()) {
+            writeln!(buf, "export type {} = {};\n", name, ts)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Emits the comment heading every generated TypeScript file.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_header(&mut buf, "".to_string(), "bin").unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), format!(
+///     "// TypeScript bindings automatically generated by bin v{} {}, DO NOT MODIFY!\n\n",
+///     env!("CARGO_PKG_VERSION"),
+///     env!("CARGO_PKG_REPOSITORY"),
+/// ));
+/// ```
+pub fn ts_header<W: Write>(buf: &mut W, now: String, bin_name: &str) -> io::Result<()> {
     writeln!(
         buf,
         "// TypeScript bindings automatically generated by {} v{} {}{}, DO NOT MODIFY!\n",
@@ -87,14 +410,97 @@ pub fn ts_prelude<W: Write>(buf: &mut W, now: String, bin_name: &str) -> io::Res
         env!("CARGO_PKG_VERSION"),
         env!("CARGO_PKG_REPOSITORY"),
         now
-    )?;
+    )
+}
 
-    let prelude = include_str!("_prelude.ts");
-    writeln!(buf, "{}", prelude)?;
+/// Emits the imports of every name exported by the TypeScript source `src` from `module`.
+/// Types and interfaces are imported with `import type`,
+/// whereas values, *i.e.*, `enum`s, constants and functions, are imported with a regular `import`.
+/// This is used to split the bindings into several files.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_import_exports(&mut buf, r#"
+/// export type A = number;
+/// export interface B {}
+/// export enum C { X }
+/// export const ASchema: z.ZodType<A> = z.number();
+/// export function PageSchema<T>(TSchema: z.ZodType<T>) {}
+/// "#, "./types").unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), r#"import type { A, B } from "./types";
+/// import { C, ASchema, PageSchema } from "./types";
+///
+/// "#);
+/// ```
+///
+/// Nothing is emitted when `src` exports nothing.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_import_exports(&mut buf, "", "./types").unwrap();
+/// assert!(buf.is_empty());
+/// ```
+pub fn ts_import_exports<W: Write>(buf: &mut W, src: &str, module: &str) -> io::Result<()> {
+    let mut types = Vec::new();
+    let mut values = Vec::new();
+    for line in src.lines() {
+        let decl = match line.strip_prefix("export ") {
+            Some(decl) => decl,
+            None => continue,
+        };
+        let (kind, rest) = decl.split_at(decl.find(' ').unwrap_or(decl.len()));
+        let name = rest
+            .trim_start()
+            .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$')
+            .next()
+            .unwrap_or("");
+        if name.is_empty() {
+            continue;
+        }
+        match kind {
+            "type" | "interface" => types.push(name),
+            "enum" | "const" | "function" | "class" => values.push(name),
+            _ => {}
+        }
+    }
+
+    if !types.is_empty() {
+        writeln!(
+            buf,
+            "import type {{ {} }} from {:?};",
+            types.join(", "),
+            module
+        )?;
+    }
+    if !values.is_empty() {
+        writeln!(buf, "import {{ {} }} from {:?};", values.join(", "), module)?;
+    }
+    if !types.is_empty() || !values.is_empty() {
+        writeln!(buf)?;
+    }
 
     Ok(())
 }
 
+/// Returns the names of the types exported by `ts_prelude` for the given `sdk` version.
+///
+/// ```
+/// use near_syn::ts::{ts_prelude_types, SdkVersion};
+///
+/// assert!(ts_prelude_types(SdkVersion::V4).contains(&"U128"));
+/// assert!(ts_prelude_types(SdkVersion::V4).contains(&"CallOptions"));
+/// assert!(ts_prelude_types(SdkVersion::V5).contains(&"NearToken"));
+/// assert!(!ts_prelude_types(SdkVersion::V5).contains(&"ValidAccountId"));
+/// ```
+pub fn ts_prelude_types(sdk: SdkVersion) -> Vec<&'static str> {
+    sdk.prelude()
+        .lines()
+        .chain(include_str!("_prelude.ts").lines())
+        .filter_map(|line| line.strip_prefix("export type "))
+        .filter_map(|decl| decl.split(' ').next())
+        .collect()
+}
+
 /// Emits additional extensions for the main type implemented by the contract.
 /// This is used when the contract implements one or more `trait`s.
 /// The `name` and `interfaces` fields must be set in order to emit these additional extensions.
@@ -170,34 +576,368 @@ pub fn ts_extend_traits<W: Write>(buf: &mut W, contract: &Contract) -> io::Resul
 /// "#);
 /// ```
 pub fn ts_contract_methods<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
-    fn fmt(methods: &Vec<String>) -> String {
-        methods
-            .iter()
-            .map(|m| format!("        {:?},\n", m))
-            .collect::<Vec<String>>()
-            .join("")
-    }
+    ts_methods_object(
+        buf,
+        &contract.name.clone().unwrap_or_default(),
+        &contract.view_methods,
+        &contract.change_methods,
+    )
+}
+
+/// Exports a `connect{Name}` function returning the contract `Name` backed by a `near-api-js` `Contract`.
+/// The returned object implements the contract interface emitted by `ts_items`,
+/// translating the `CallOptions` of change methods to the options expected by `near-api-js`.
+/// It expects the `{Name}Methods` object emitted by `ts_contract_methods`,
+/// and the imports emitted by `ts_prelude` when `TsOptions::factory` is set.
+/// Nothing is emitted when the contract `name` is unknown.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[near_bindgen]
+///     impl Contract {
+///         pub fn get(&self) -> u32 { 42 }
+///         pub fn set(&mut self, value: u32) {}
+///         #[payable]
+///         pub fn buy(&mut self) {}
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_contract_factory(&mut buf, &contract);
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"
+/// function nearCallOptions(options: PayableCallOptions = {}) {
+///     return {
+///         gas: options.gas === undefined ? undefined : new BN(options.gas.toString()),
+///         amount: options.attachedDeposit === undefined ? undefined : new BN(options.attachedDeposit),
+///         meta: options.walletMeta,
+///         callbackUrl: options.walletCallbackUrl,
+///     };
+/// }
+///
+/// export function connectContract(account: Account, contractId: string): Contract {
+///     const contract: any = new NearContract(account, contractId, ContractMethods);
+///     return {
+///         get: () => contract.get({}),
+///         set: (args, options) => contract.set({ args, ...nearCallOptions(options) }),
+///         buy: (options) => contract.buy({ args: {}, ...nearCallOptions(options) }),
+///     } as Contract;
+/// }
+/// "#);
+/// ```
+pub fn ts_contract_factory<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
+    let name = match &contract.name {
+        Some(name) => name,
+        None => return Ok(()),
+    };
 
     writeln!(
         buf,
-        "export const {}Methods = {{",
-        contract.name.clone().unwrap_or_default()
+        "\nfunction nearCallOptions(options: PayableCallOptions = {{}}) {{"
+    )?;
+    writeln!(buf, "    return {{")?;
+    writeln!(
+        buf,
+        "        gas: options.gas === undefined ? undefined : new BN(options.gas.toString()),"
+    )?;
+    writeln!(buf, "        amount: options.attachedDeposit === undefined ? undefined : new BN(options.attachedDeposit),")?;
+    writeln!(buf, "        meta: options.walletMeta,")?;
+    writeln!(buf, "        callbackUrl: options.walletCallbackUrl,")?;
+    writeln!(buf, "    }};")?;
+    writeln!(buf, "}}\n")?;
+
+    writeln!(
+        buf,
+        "export function connect{0}(account: Account, contractId: string): {0} {{",
+        name
+    )?;
+    writeln!(
+        buf,
+        "    const contract: any = new NearContract(account, contractId, {}Methods);",
+        name
     )?;
+    writeln!(buf, "    return {{")?;
+    for method_name in &contract.view_methods {
+        if let Some((method, _, _)) = contract.methods.get(method_name) {
+            if method.input_args().is_empty() {
+                writeln!(buf, "        {0}: () => contract.{0}({{}}),", method_name)?;
+            } else {
+                writeln!(
+                    buf,
+                    "        {0}: (args) => contract.{0}(args),",
+                    method_name
+                )?;
+            }
+        }
+    }
+    for method_name in &contract.change_methods {
+        if let Some((method, _, _)) = contract.methods.get(method_name) {
+            if method.input_args().is_empty() {
+                writeln!(
+                    buf,
+                    "        {0}: (options) => contract.{0}({{ args: {{}}, ...nearCallOptions(options) }}),",
+                    method_name
+                )?;
+            } else {
+                writeln!(
+                    buf,
+                    "        {0}: (args, options) => contract.{0}({{ args, ...nearCallOptions(options) }}),",
+                    method_name
+                )?;
+            }
+        }
+    }
+    writeln!(buf, "    }} as {};", name)?;
+    writeln!(buf, "}}")?;
+
+    Ok(())
+}
+
+/// Exports the `{Name}Actions` object with a builder for each change method of the contract `Name`.
+/// Each builder returns the `FunctionCall` action calling its method,
+/// as expected by `@near-wallet-selector`, so that batched transactions can be composed type-safely.
+/// Unless given in the `CallOptions`, actions attach 30 Tgas and no deposit.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[near_bindgen]
+///     impl Contract {
+///         pub fn set(&mut self, value: u32) {}
+///         #[payable]
+///         pub fn buy(&mut self) {}
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_contract_actions(&mut buf, &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"
+/// /**
+///  * A `FunctionCall` action, as expected by `@near-wallet-selector`.
+///  */
+/// export type FunctionCallAction<A> = {
+///     type: "FunctionCall";
+///     params: {
+///         methodName: string;
+///         args: A;
+///         gas: string;
+///         deposit: string;
+///     };
+/// };
+///
+/// const DEFAULT_GAS = "30000000000000";
+///
+/// export const ContractActions = {
+///     set(args: { value: number }, options: CallOptions = {}): FunctionCallAction<{ value: number }> {
+///         return {
+///             type: "FunctionCall",
+///             params: {
+///                 methodName: "set",
+///                 args,
+///                 gas: (options.gas ?? DEFAULT_GAS).toString(),
+///                 deposit: "0",
+///             },
+///         };
+///     },
+///     buy(options: PayableCallOptions = {}): FunctionCallAction<{}> {
+///         return {
+///             type: "FunctionCall",
+///             params: {
+///                 methodName: "buy",
+///                 args: {},
+///                 gas: (options.gas ?? DEFAULT_GAS).toString(),
+///                 deposit: options.attachedDeposit ?? "0",
+///             },
+///         };
+///     },
+/// };
+/// "#);
+/// ```
+pub fn ts_contract_actions<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    writeln!(buf, "\n/**")?;
     writeln!(
         buf,
-        "    viewMethods: [\n{}    ],",
-        fmt(&contract.view_methods)
+        " * A `FunctionCall` action, as expected by `@near-wallet-selector`."
     )?;
+    writeln!(buf, " */")?;
+    writeln!(buf, "export type FunctionCallAction<A> = {{")?;
+    writeln!(buf, "    type: \"FunctionCall\";")?;
+    writeln!(buf, "    params: {{")?;
+    writeln!(buf, "        methodName: string;")?;
+    writeln!(buf, "        args: A;")?;
+    writeln!(buf, "        gas: string;")?;
+    writeln!(buf, "        deposit: string;")?;
+    writeln!(buf, "    }};")?;
+    writeln!(buf, "}};\n")?;
+    writeln!(buf, "const DEFAULT_GAS = \"30000000000000\";\n")?;
+
     writeln!(
         buf,
-        "    changeMethods: [\n{}    ],",
-        fmt(&contract.change_methods)
+        "export const {}Actions = {{",
+        contract.name.clone().unwrap_or_default()
     )?;
+    for name in &contract.change_methods {
+        if let Some((method, _, file)) = contract.methods.get(name) {
+            let args_type = ts_args_type(method).map_err(|err| err.in_file(file.as_deref()))?;
+            let (options_type, deposit) = if method.is_payable() {
+                ("PayableCallOptions", "options.attachedDeposit ?? \"0\"")
+            } else {
+                ("CallOptions", "\"0\"")
+            };
+
+            if let Some(args_type) = &args_type {
+                writeln!(
+                    buf,
+                    "    {}(args: {}, options: {} = {{}}): FunctionCallAction<{}> {{",
+                    name, args_type, options_type, args_type
+                )?;
+            } else {
+                writeln!(
+                    buf,
+                    "    {}(options: {} = {{}}): FunctionCallAction<{{}}> {{",
+                    name, options_type
+                )?;
+            }
+            writeln!(buf, "        return {{")?;
+            writeln!(buf, "            type: \"FunctionCall\",")?;
+            writeln!(buf, "            params: {{")?;
+            writeln!(buf, "                methodName: {:?},", name)?;
+            if args_type.is_some() {
+                writeln!(buf, "                args,")?;
+            } else {
+                writeln!(buf, "                args: {{}},")?;
+            }
+            writeln!(
+                buf,
+                "                gas: (options.gas ?? DEFAULT_GAS).toString(),"
+            )?;
+            writeln!(buf, "                deposit: {},", deposit)?;
+            writeln!(buf, "            }},")?;
+            writeln!(buf, "        }};")?;
+            writeln!(buf, "    }},")?;
+        }
+    }
     writeln!(buf, "}};")?;
 
     Ok(())
 }
 
+/// Exports the `near-api-js` methods object named `{name}Methods`.
+fn ts_methods_object<W: Write>(
+    buf: &mut W,
+    name: &str,
+    view_methods: &[String],
+    change_methods: &[String],
+) -> io::Result<()> {
+    fn fmt(methods: &[String]) -> String {
+        methods
+            .iter()
+            .map(|m| format!("        {:?},\n", m))
+            .collect::<Vec<String>>()
+            .join("")
+    }
+
+    writeln!(buf, "export const {}Methods = {{", name)?;
+    writeln!(buf, "    viewMethods: [\n{}    ],", fmt(view_methods))?;
+    writeln!(buf, "    changeMethods: [\n{}    ],", fmt(change_methods))?;
+    writeln!(buf, "}};")?;
+
+    Ok(())
+}
+
+/// Exports an interface and a methods object for each `ext_contract` trait in `contract`,
+/// so that external contracts can be called with the same typings.
+/// See `ts_ext_contract`.
+pub fn ts_ext_contracts<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    for trait_name in &contract.ext_contracts {
+        if let Some(item_trait) = contract.traits.get(trait_name) {
+            ts_ext_contract(buf, item_trait, contract)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Exports the interface and the methods object of an external contract,
+/// declared by the `item_trait` marked as `#[ext_contract(name)]`.
+/// The interface is named after the trait, while the methods object is named after `name`,
+/// or after the trait when the `ext_contract` attribute has no name.
+/// When the trait is also implemented by `contract`, its interface is already emitted by `ts_impl`,
+/// thus only the methods object is exported.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     /// Fungible token standard.
+///     #[ext_contract(ext_ft)]
+///     trait FungibleToken {
+///         fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128);
+///         fn ft_balance_of(&self, account_id: AccountId) -> U128;
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_ext_contract(&mut buf, contract.traits.get("FungibleToken").unwrap(), &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"
+/// /**
+///  * Fungible token standard.
+///  */
+/// export interface FungibleToken {
+///     /**
+///      */
+///     ft_transfer(args: { receiver_id: AccountId, amount: U128 }, options?: CallOptions): Promise<void>;
+///
+///     /**
+///      */
+///     ft_balance_of(args: { account_id: AccountId }): Promise<U128>;
+///
+/// }
+///
+/// export const ext_ftMethods = {
+///     viewMethods: [
+///         "ft_balance_of",
+///     ],
+///     changeMethods: [
+///         "ft_transfer",
+///     ],
+/// };
+/// "#);
+/// ```
+pub fn ts_ext_contract<W: Write>(
+    buf: &mut W,
+    item_trait: &NearItemTrait,
+    contract: &Contract,
+) -> Result<()> {
+    let trait_name = item_trait.ident.to_string();
+    let name = match item_trait.ext_contract_name() {
+        Some(name) => name.to_string(),
+        None => return Ok(()),
+    };
+    let methods = item_trait.impl_methods();
+
+    writeln!(buf)?;
+    if !contract.interfaces.contains(&trait_name) {
+        ts_doc(buf, &item_trait.attrs, "")?;
+        writeln!(buf, "export interface {} {{", trait_name)?;
+        for method in &methods {
+            ts_doc(buf, &method.attrs, "    ")?;
+            writeln!(buf, "    {}\n", ts_sig(method)?)?;
+        }
+        writeln!(buf, "}}\n")?;
+    }
+
+    let mut view_methods = Vec::new();
+    let mut change_methods = Vec::new();
+    for method in &methods {
+        if method.is_init() || method.is_mut() {
+            &mut change_methods
+        } else {
+            &mut view_methods
+        }
+        .push(method.sig.ident.to_string());
+    }
+    ts_methods_object(buf, &name, &view_methods, &change_methods)?;
+
+    Ok(())
+}
+
 /// Translates a collection of Rust items to TypeScript.
 /// It currently translates `type`, `struct`, `enum` and `impl` items to TypeScript.
 /// It traverses recursively `mod` definitions with braced content.
@@ -223,12 +963,12 @@ pub fn ts_contract_methods<W: Write>(buf: &mut W, contract: &Contract) -> io::Re
 /// r#"/**
 ///  * Doc-comments are translated.
 ///  */
-/// export type T = number;
+/// export type T = RawU64;
 ///
 /// /**
 ///  * Doc-comments are translated.
 ///  */
-/// export type S = number;
+/// export type S = RawU64;
 ///
 /// "#);
 /// ```
@@ -284,18 +1024,58 @@ pub fn ts_contract_methods<W: Write>(buf: &mut W, contract: &Contract) -> io::Re
 ///
 /// }
 ///
-/// export interface Contract extends NftCore, NftEnum {}
+/// export interface Contract extends NftCore, NftEnum {}
+///
+/// "#);
+/// ```
+pub fn ts_items<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    ts_items_where(buf, contract, |_| true)
+}
+
+/// Emits only the `struct`s, `enum`s and type aliases of the `contract`, see `ts_items`.
+/// Along with `ts_impl_items`, this allows to emit the types and the contract interfaces into separate files.
+///
+/// ```
+/// let ast: syn::File = syn::parse2(quote::quote! {
+///         #[near_bindgen]
+///         impl Contract {
+///             pub fn get(&self) -> T { 42 }
+///         }
+///
+///         type T = u32;
+///     }).unwrap();
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(ast);
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_type_items(&mut buf, &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf), "/**\n */\nexport type T = number;\n\n");
 ///
-/// "#);
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_impl_items(&mut buf, &contract).unwrap();
+/// assert!(String::from_utf8_lossy(&buf).starts_with("/**\n */\nexport interface Contract {\n"));
 /// ```
-pub fn ts_items<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
-    for item in &contract.items {
-        match item {
-            NearItem::Impl(item_impl) => ts_impl(buf, &item_impl, contract)?,
-            NearItem::Struct(item_struct) => ts_struct(buf, &item_struct)?,
-            NearItem::Enum(item_enum) => ts_enum(buf, &item_enum)?,
-            NearItem::Type(item_type) => ts_typedef(buf, &item_type)?,
+pub fn ts_type_items<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    ts_items_where(buf, contract, |item| !matches!(item, NearItem::Impl(_)))
+}
+
+/// Emits only the `impl`s of the `contract`, see `ts_items` and `ts_type_items`.
+pub fn ts_impl_items<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    ts_items_where(buf, contract, |item| matches!(item, NearItem::Impl(_)))
+}
+
+fn ts_items_where<W: Write>(
+    buf: &mut W,
+    contract: &Contract,
+    pred: impl Fn(&NearItem) -> bool,
+) -> Result<()> {
+    for item in contract.items.iter().filter(|item| pred(item)) {
+        match &**item {
+            NearItem::Impl(item_impl) => ts_impl(buf, item_impl, contract),
+            NearItem::Struct(item_struct) => ts_struct(buf, item_struct),
+            NearItem::Enum(item_enum) => ts_enum(buf, item_enum),
+            NearItem::Type(item_type) => ts_typedef(buf, item_type),
         }
+        .map_err(|err| err.in_file(item.file.as_deref()))?;
     }
 
     Ok(())
@@ -332,7 +1112,20 @@ pub fn ts_items<W: Write>(buf: &mut W, contract: &Contract) -> io::Result<()> {
 ///
 /// "#);
 /// ```
-pub fn ts_impl<W: Write>(buf: &mut W, item_impl: &ItemImpl, contract: &Contract) -> io::Result<()> {
+///
+/// The `impl` type must be a named type.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let result = near_syn::ts::ts_impl(&mut buf, &syn::parse2(quote::quote! {
+///         #[near_bindgen]
+///         impl *const u32 {
+///             pub fn get(&self) -> u32 { 42 }
+///         }
+///     }).unwrap(), &near_syn::contract::Contract::new());
+/// assert!(matches!(result, Err(near_syn::Error::UnsupportedImpl { .. })));
+/// ```
+pub fn ts_impl<W: Write>(buf: &mut W, item_impl: &ItemImpl, contract: &Contract) -> Result<()> {
     if let Some(methods) = item_impl.bindgen_methods() {
         let mut item_trait = None;
         if let Some(trait_name) = item_impl.get_trait_name() {
@@ -344,13 +1137,15 @@ pub fn ts_impl<W: Write>(buf: &mut W, item_impl: &ItemImpl, contract: &Contract)
                 ts_doc(buf, &item_impl.attrs, "")?;
                 writeln!(buf, "export interface {} {{", impl_name)?;
             } else {
-                panic!("Impl struct name not supported")
+                return Err(Error::UnsupportedImpl {
+                    location: Location::of(&item_impl.self_ty),
+                });
             }
         }
 
         for method in methods {
             ts_doc(buf, &method.join_attrs(item_trait), "    ")?;
-            writeln!(buf, "    {}\n", ts_sig(&method))?;
+            writeln!(buf, "    {}\n", ts_sig(method)?)?;
         }
 
         writeln!(buf, "}}\n")?;
@@ -391,6 +1186,70 @@ pub fn ts_impl<W: Write>(buf: &mut W, item_impl: &ItemImpl, contract: &Contract)
 /// "#);
 /// ```
 ///
+/// Field names follow `serde` attributes, *i.e.*, `rename` and `rename_all`.
+/// Fields with `default` or `skip_serializing_if` are optional,
+/// `skip`ped fields are omitted and `flatten`ed fields are intersected.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_struct(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         #[serde(rename_all = "camelCase")]
+///         struct A {
+///             owner_id: AccountId,
+///             #[serde(skip_serializing_if = "Option::is_none")]
+///             memo: Option<String>,
+///             #[serde(skip)]
+///             cache: u32,
+///             #[serde(flatten)]
+///             metadata: Metadata,
+///         }
+///     }).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"/**
+///  */
+/// export type A = {
+///     /**
+///      */
+///     ownerId: AccountId;
+///
+///     /**
+///      */
+///     memo?: string|null;
+///
+/// } & Metadata
+///
+/// "#);
+/// ```
+///
+/// Generic `struct`s are translated to generic TypeScript types.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_struct(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         struct Page<T, C = U64> {
+///             items: Vec<T>,
+///             cursor: Option<C>,
+///         }
+///     }).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"/**
+///  */
+/// export type Page<T, C = U64> = {
+///     /**
+///      */
+///     items: T[];
+///
+///     /**
+///      */
+///     cursor: C|null;
+///
+/// }
+///
+/// "#);
+/// ```
+///
 /// Single-compoenent tuple-structs are converted to TypeScript type synonym.
 ///
 /// ```
@@ -438,31 +1297,52 @@ pub fn ts_impl<W: Write>(buf: &mut W, item_impl: &ItemImpl, contract: &Contract)
 ///     }).unwrap());
 /// assert_eq!(String::from_utf8_lossy(&buf), "");
 /// ```
-pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> io::Result<()> {
+///
+/// Unit `struct`s are not supported.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let result = near_syn::ts::ts_struct(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         struct A;
+///     }).unwrap());
+/// assert_eq!(result.unwrap_err().to_string(), "unit struct not supported: `A`");
+/// ```
+pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> Result<()> {
     if item_struct.is_serde() {
+        let generics = ts_generics(&item_struct.generics)?;
         ts_doc(buf, &item_struct.attrs, "")?;
         match &item_struct.fields {
             Fields::Named(fields) => {
-                writeln!(buf, "export type {} = {{", item_struct.ident)?;
-                for field in &fields.named {
-                    let field_name = field.ident.as_ref().unwrap();
-                    let ty = ts_type(&field.ty);
-                    ts_doc(buf, &field.attrs, "    ")?;
-                    writeln!(buf, "    {}: {};\n", field_name, ty)?;
+                writeln!(buf, "export type {}{} = {{", item_struct.ident, generics)?;
+                let mut flattened = Vec::new();
+                for field in serde_fields(fields, &item_struct.attrs) {
+                    let ty = ts_type(&field.field.ty)?;
+                    if field.flatten {
+                        flattened.push(ty);
+                        continue;
+                    }
+                    ts_doc(buf, &field.field.attrs, "    ")?;
+                    writeln!(buf, "    {}: {};\n", ts_field_name(&field), ty)?;
                 }
-                writeln!(buf, "}}")?;
+                write!(buf, "}}")?;
+                for ty in flattened {
+                    write!(buf, " & {}", ty)?;
+                }
+                writeln!(buf)?;
                 writeln!(buf, "")?;
             }
             Fields::Unnamed(fields) => {
                 let mut tys = Vec::new();
                 for field in &fields.unnamed {
-                    let ty = ts_type(&field.ty);
+                    let ty = ts_type(&field.ty)?;
                     tys.push(ty);
                 }
                 writeln!(
                     buf,
-                    "export type {} = {};\n",
+                    "export type {}{} = {};\n",
                     item_struct.ident,
+                    generics,
                     if tys.len() == 1 {
                         tys.get(0).unwrap().clone()
                     } else {
@@ -470,7 +1350,12 @@ pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> io::Result<
                     }
                 )?;
             }
-            Fields::Unit => panic!("unit struct no supported"),
+            Fields::Unit => {
+                return Err(Error::UnitStruct {
+                    name: item_struct.ident.to_string(),
+                    location: Location::of(item_struct),
+                })
+            }
         }
     }
 
@@ -485,6 +1370,7 @@ pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> io::Result<
 /// ### Examples
 ///
 /// For instance, a plain Rust `enum` will be translated to an `enum`.
+/// Since `serde` serializes unit variants by their name, each member is initialized to it.
 ///
 /// ```
 /// let mut buf = Vec::new();
@@ -495,7 +1381,7 @@ pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> io::Result<
 ///             /// Doc-comments here are translated as well.
 ///             V1,
 ///         }
-///     }).unwrap());
+///     }).unwrap()).unwrap();
 /// assert_eq!(String::from_utf8_lossy(&buf),
 /// r#"/**
 ///  * Doc-comments are translated.
@@ -504,26 +1390,247 @@ pub fn ts_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> io::Result<
 ///     /**
 ///      * Doc-comments here are translated as well.
 ///      */
-///     V1,
+///     V1 = "V1",
 ///
 /// }
 ///
 /// "#);
 /// ```
-pub fn ts_enum<W: Write>(buf: &mut W, item_enum: &ItemEnum) -> io::Result<()> {
-    if item_enum.is_serde() {
-        ts_doc(buf, &item_enum.attrs, "")?;
+///
+/// When variants carry data, the `enum` is translated to a union type
+/// following `serde`'s externally tagged representation.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_enum(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         enum Action {
+///             Transfer { to: AccountId, amount: U128 },
+///             Burn(U128),
+///             Swap(U128, U128),
+///             Stop,
+///         }
+///     }).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"/**
+///  */
+/// export type Action =
+///     /**
+///      */
+///     | { Transfer: { to: AccountId; amount: U128 } }
+///
+///     /**
+///      */
+///     | { Burn: U128 }
+///
+///     /**
+///      */
+///     | { Swap: [U128, U128] }
+///
+///     /**
+///      */
+///     | "Stop";
+///
+/// "#);
+/// ```
+///
+/// The internally tagged `#[serde(tag = "...")]`,
+/// adjacently tagged `#[serde(tag = "...", content = "...")]` and
+/// `#[serde(untagged)]` representations are supported as well.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::ts::ts_enum(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         #[serde(tag = "type")]
+///         enum Action {
+///             Transfer { to: AccountId },
+///             Stop,
+///         }
+///     }).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"/**
+///  */
+/// export type Action =
+///     /**
+///      */
+///     | { type: "Transfer"; to: AccountId }
+///
+///     /**
+///      */
+///     | { type: "Stop" };
+///
+/// "#);
+/// ```
+///
+/// ## Errors
+///
+/// `serde` does not support tuple variants in internally tagged `enum`s.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let result = near_syn::ts::ts_enum(&mut buf, &syn::parse2(quote::quote! {
+///         #[derive(Serialize)]
+///         #[serde(tag = "type")]
+///         enum E { V(u8, u8) }
+///     }).unwrap());
+/// assert_eq!(result.unwrap_err().to_string(), "tuple variant not supported in internally tagged enum: `V`");
+/// ```
+pub fn ts_enum<W: Write>(buf: &mut W, item_enum: &ItemEnum) -> Result<()> {
+    if !item_enum.is_serde() {
+        return Ok(());
+    }
+
+    let repr = serde_enum_repr(&item_enum.attrs);
+    let variants = serde_variants(&item_enum.variants, &item_enum.attrs);
+    let is_plain = variants
+        .iter()
+        .all(|(variant, _)| variant.fields == Fields::Unit);
+
+    ts_doc(buf, &item_enum.attrs, "")?;
+    if is_plain && repr == SerdeEnumRepr::External {
         writeln!(buf, "export enum {} {{", item_enum.ident)?;
-        for variant in &item_enum.variants {
+        for (variant, name) in &variants {
             ts_doc(buf, &variant.attrs, "    ")?;
-            writeln!(buf, "    {},\n", variant.ident)?;
+            writeln!(buf, "    {} = {:?},\n", variant.ident, name)?;
         }
         writeln!(buf, "}}\n")?;
+    } else {
+        writeln!(
+            buf,
+            "export type {}{} =",
+            item_enum.ident,
+            ts_generics(&item_enum.generics)?
+        )?;
+        let last = variants.len().saturating_sub(1);
+        for (i, (variant, name)) in variants.iter().enumerate() {
+            ts_doc(buf, &variant.attrs, "    ")?;
+            let end = if i == last { ";" } else { "" };
+            writeln!(buf, "    | {}{}\n", ts_variant(variant, name, &repr)?, end)?;
+        }
     }
 
     Ok(())
 }
 
+/// Translates an `enum` `variant` named `name` in JSON according to the `serde` representation `repr`.
+fn ts_variant(variant: &Variant, name: &str, repr: &SerdeEnumRepr) -> Result<String> {
+    Ok(match repr {
+        SerdeEnumRepr::External => match &variant.fields {
+            Fields::Unit => format!("{:?}", name),
+            fields => format!(
+                "{{ {}: {} }}",
+                ts_prop_name(name),
+                ts_fields(fields, &variant.attrs)?
+            ),
+        },
+        SerdeEnumRepr::Internal { tag } => {
+            let tag = format!("{}: {:?}", ts_prop_name(tag), name);
+            match &variant.fields {
+                Fields::Unit => format!("{{ {} }}", tag),
+                Fields::Named(fields) => ts_object(vec![tag], fields, &variant.attrs)?,
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    format!("{{ {} }} & {}", tag, ts_type(&fields.unnamed[0].ty)?)
+                }
+                Fields::Unnamed(_) => {
+                    return Err(Error::TupleVariant {
+                        name: name.to_string(),
+                        location: Location::of(variant),
+                    })
+                }
+            }
+        }
+        SerdeEnumRepr::Adjacent { tag, content } => match &variant.fields {
+            Fields::Unit => format!("{{ {}: {:?} }}", ts_prop_name(tag), name),
+            fields => format!(
+                "{{ {}: {:?}; {}: {} }}",
+                ts_prop_name(tag),
+                name,
+                ts_prop_name(content),
+                ts_fields(fields, &variant.attrs)?
+            ),
+        },
+        SerdeEnumRepr::Untagged => match &variant.fields {
+            Fields::Unit => "null".to_string(),
+            fields => ts_fields(fields, &variant.attrs)?,
+        },
+    })
+}
+
+/// Translates the `fields` of an `enum` variant with attributes `attrs` into an inline TypeScript type.
+/// A single unnamed field is translated to the type of that field.
+fn ts_fields(fields: &Fields, attrs: &[Attribute]) -> Result<String> {
+    Ok(match fields {
+        Fields::Named(fields) => ts_object(Vec::new(), fields, attrs)?,
+        Fields::Unnamed(fields) => {
+            let tys = fields
+                .unnamed
+                .iter()
+                .map(|field| ts_type(&field.ty))
+                .collect::<Result<Vec<String>>>()?;
+            if tys.len() == 1 {
+                tys[0].clone()
+            } else {
+                format!("[{}]", tys.join(", "))
+            }
+        }
+        Fields::Unit => "null".to_string(),
+    })
+}
+
+/// Translates named `fields` into an inline TypeScript object type, *e.g.*, `{ to: AccountId }`,
+/// after the given `members`.
+/// Flattened fields are intersected with the object type.
+fn ts_object(
+    mut members: Vec<String>,
+    fields: &FieldsNamed,
+    attrs: &[Attribute],
+) -> Result<String> {
+    let mut flattened = Vec::new();
+    for field in serde_fields(fields, attrs) {
+        let ty = ts_type(&field.field.ty)?;
+        if field.flatten {
+            flattened.push(ty);
+        } else {
+            members.push(format!("{}: {}", ts_field_name(&field), ty));
+        }
+    }
+
+    let mut object = if members.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", members.join("; "))
+    };
+    for ty in flattened {
+        object.push_str(&format!(" & {}", ty));
+    }
+
+    Ok(object)
+}
+
+/// Returns the TypeScript property name of `field`, marked with `?` when the field is optional.
+fn ts_field_name(field: &SerdeField) -> String {
+    let optional = if field.optional { "?" } else { "" };
+    format!("{}{}", ts_prop_name(&field.name), optional)
+}
+
+/// Quotes `name` when it is not a valid TypeScript identifier, *e.g.*, `"kebab-case"`.
+///
+/// ```
+/// assert_eq!(near_syn::ts::ts_prop_name("owner_id"), "owner_id");
+/// assert_eq!(near_syn::ts::ts_prop_name("kebab-case"), "\"kebab-case\"");
+/// ```
+pub fn ts_prop_name(name: &str) -> String {
+    let is_ident = name.chars().enumerate().all(|(i, c)| {
+        c == '_' || c == '$' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+    });
+    if is_ident && !name.is_empty() {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
 /// Translates a type alias to another type alias in TypeScript.
 ///
 /// ### Examples
@@ -538,7 +1645,7 @@ pub fn ts_enum<W: Write>(buf: &mut W, item_enum: &ItemEnum) -> io::Result<()> {
 /// r#"/**
 ///  * Doc-comments are translated.
 ///  */
-/// export type T = number;
+/// export type T = RawU64;
 ///
 /// "#);
 /// ```
@@ -554,23 +1661,51 @@ pub fn ts_enum<W: Write>(buf: &mut W, item_enum: &ItemEnum) -> io::Result<()> {
 /// assert_eq!(String::from_utf8_lossy(&buf),
 /// r#"/**
 ///  */
-/// export type T = number;
+/// export type T = RawU64;
 ///
 /// "#);
 /// ```
-pub fn ts_typedef<W: Write>(buf: &mut W, item_type: &syn::ItemType) -> io::Result<()> {
+pub fn ts_typedef<W: Write>(buf: &mut W, item_type: &syn::ItemType) -> Result<()> {
     ts_doc(buf, &item_type.attrs, "")?;
     writeln!(
         buf,
-        "export type {} = {};",
+        "export type {}{} = {};",
         item_type.ident,
-        ts_type(&item_type.ty)
+        ts_generics(&item_type.generics)?,
+        ts_type(&item_type.ty)?
     )?;
     writeln!(buf, "")?;
 
     Ok(())
 }
 
+/// Translates the type parameters in `generics` into TypeScript type parameters,
+/// including their defaults.
+/// Lifetimes, `const` parameters and trait bounds are omitted.
+/// Returns an empty string when there are no type parameters.
+///
+/// ### Examples
+///
+/// ```
+/// let generics = syn::parse_str::<syn::ItemStruct>("struct A<'a, T: Clone, U = Vec<u8>>;").unwrap().generics;
+/// assert_eq!(near_syn::ts::ts_generics(&generics).unwrap(), "<T, U = number[]>");
+/// ```
+pub fn ts_generics(generics: &Generics) -> Result<String> {
+    let mut params = Vec::new();
+    for param in generics.type_params() {
+        params.push(match &param.default {
+            Some(default) => format!("{} = {}", param.ident, ts_type(default)?),
+            None => param.ident.to_string(),
+        });
+    }
+
+    Ok(if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    })
+}
+
 /// Translates `doc` attributes into TypeScript docs.
 /// The `indent` argument is used as a prefix for each line emitted.
 ///
@@ -588,7 +1723,7 @@ pub fn ts_typedef<W: Write>(buf: &mut W, item_type: &syn::ItemType) -> io::Resul
 ///      */
 /// "#);
 /// ```
-pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io::Result<()> {
+pub fn ts_doc<W: Write>(buf: &mut W, attrs: &[Attribute], indent: &str) -> io::Result<()> {
     writeln!(buf, "{}/**", indent)?;
     write_docs(buf, attrs, |l| format!("{} * {}", indent, l.trim_start()))?;
     writeln!(buf, "{} */", indent)?;
@@ -606,10 +1741,23 @@ pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io
 /// use syn::parse_str;
 /// use near_syn::ts::ts_type;
 ///
-/// assert_eq!(ts_type(&parse_str("bool").unwrap()), "boolean");
-/// assert_eq!(ts_type(&parse_str("u8").unwrap()), "number");
-/// assert_eq!(ts_type(&parse_str("i64").unwrap()), "number");
-/// assert_eq!(ts_type(&parse_str("String").unwrap()), "string");
+/// assert_eq!(ts_type(&parse_str("bool").unwrap()).unwrap(), "boolean");
+/// assert_eq!(ts_type(&parse_str("u8").unwrap()).unwrap(), "number");
+/// assert_eq!(ts_type(&parse_str("usize").unwrap()).unwrap(), "number");
+/// assert_eq!(ts_type(&parse_str("f64").unwrap()).unwrap(), "number");
+/// assert_eq!(ts_type(&parse_str("char").unwrap()).unwrap(), "string");
+/// assert_eq!(ts_type(&parse_str("String").unwrap()).unwrap(), "string");
+/// ```
+///
+/// Raw 64 and 128 bits integers are serialized as JSON numbers,
+/// which lose precision above `Number.MAX_SAFE_INTEGER`.
+/// They are flagged with the `RawU64`, `RawI64`, `RawU128` and `RawI128` aliases exported by `ts_prelude`.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::ts::ts_type;
+/// assert_eq!(ts_type(&parse_str("u64").unwrap()).unwrap(), "RawU64");
+/// assert_eq!(ts_type(&parse_str("i128").unwrap()).unwrap(), "RawI128");
 /// ```
 ///
 /// Rust shared references are supported as well.
@@ -617,10 +1765,19 @@ pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io
 /// ```
 /// # use syn::parse_str;
 /// # use near_syn::ts::ts_type;
-/// assert_eq!(ts_type(&parse_str("&String").unwrap()), "string");
-/// assert_eq!(ts_type(&parse_str("&bool").unwrap()), "boolean");
-/// assert_eq!(ts_type(&parse_str("&u32").unwrap()), "number");
-/// assert_eq!(ts_type(&parse_str("&TokenId").unwrap()), "TokenId");
+/// assert_eq!(ts_type(&parse_str("&String").unwrap()).unwrap(), "string");
+/// assert_eq!(ts_type(&parse_str("&bool").unwrap()).unwrap(), "boolean");
+/// assert_eq!(ts_type(&parse_str("&u32").unwrap()).unwrap(), "number");
+/// assert_eq!(ts_type(&parse_str("&TokenId").unwrap()).unwrap(), "TokenId");
+/// ```
+///
+/// Generic arguments of user-defined types are kept.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::ts::ts_type;
+/// assert_eq!(ts_type(&parse_str("Page<Token>").unwrap()).unwrap(), "Page<Token>");
+/// assert_eq!(ts_type(&parse_str("Pair<'a, U64, Vec<u8>>").unwrap()).unwrap(), "Pair<U64, number[]>");
 /// ```
 ///
 /// Rust standard and collections types, *e.g.*, `Option`, `Vec` and `HashMap`,
@@ -629,10 +1786,27 @@ pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io
 /// ```
 /// # use syn::parse_str;
 /// # use near_syn::ts::ts_type;
-/// assert_eq!(ts_type(&parse_str("Option<U64>").unwrap()), "U64|null");
-/// assert_eq!(ts_type(&parse_str("Vec<ValidAccountId>").unwrap()), "ValidAccountId[]");
-/// assert_eq!(ts_type(&parse_str("HashSet<ValidAccountId>").unwrap()), "ValidAccountId[]");
-/// assert_eq!(ts_type(&parse_str("BTreeMap<AccountId, U128>").unwrap()), "Record<AccountId, U128>");
+/// assert_eq!(ts_type(&parse_str("Option<U64>").unwrap()).unwrap(), "U64|null");
+/// assert_eq!(ts_type(&parse_str("Vec<ValidAccountId>").unwrap()).unwrap(), "ValidAccountId[]");
+/// assert_eq!(ts_type(&parse_str("HashSet<ValidAccountId>").unwrap()).unwrap(), "ValidAccountId[]");
+/// assert_eq!(ts_type(&parse_str("BTreeMap<AccountId, U128>").unwrap()).unwrap(), "Record<AccountId, U128>");
+/// assert_eq!(ts_type(&parse_str("VecDeque<U64>").unwrap()).unwrap(), "U64[]");
+/// assert_eq!(ts_type(&parse_str("IndexMap<AccountId, U128>").unwrap()).unwrap(), "Record<AccountId, U128>");
+/// assert_eq!(ts_type(&parse_str("Vec<u8>").unwrap()).unwrap(), "number[]");
+/// assert_eq!(ts_type(&parse_str("serde_json::Value").unwrap()).unwrap(), "unknown");
+/// ```
+///
+/// Smart pointers are translated to the type they point to,
+/// and slices to arrays.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::ts::ts_type;
+/// assert_eq!(ts_type(&parse_str("Box<Token>").unwrap()).unwrap(), "Token");
+/// assert_eq!(ts_type(&parse_str("Rc<Token>").unwrap()).unwrap(), "Token");
+/// assert_eq!(ts_type(&parse_str("Arc<String>").unwrap()).unwrap(), "string");
+/// assert_eq!(ts_type(&parse_str("Cow<'a, str>").unwrap()).unwrap(), "string");
+/// assert_eq!(ts_type(&parse_str("&[Option<U64>]").unwrap()).unwrap(), "(U64|null)[]");
 /// ```
 ///
 /// Rust nested types are converted to TypeScript as well.
@@ -640,10 +1814,10 @@ pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io
 /// ```
 /// # use syn::parse_str;
 /// # use near_syn::ts::ts_type;
-/// assert_eq!(ts_type(&parse_str("HashMap<AccountId, Vec<U128>>").unwrap()), "Record<AccountId, U128[]>");
-/// assert_eq!(ts_type(&parse_str("Vec<Option<U128>>").unwrap()), "(U128|null)[]");
-/// assert_eq!(ts_type(&parse_str("Vec<Vec<U64>>").unwrap()), "U64[][]");
-/// assert_eq!(ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()), "[U64, string, number[]]");
+/// assert_eq!(ts_type(&parse_str("HashMap<AccountId, Vec<U128>>").unwrap()).unwrap(), "Record<AccountId, U128[]>");
+/// assert_eq!(ts_type(&parse_str("Vec<Option<U128>>").unwrap()).unwrap(), "(U128|null)[]");
+/// assert_eq!(ts_type(&parse_str("Vec<Vec<U64>>").unwrap()).unwrap(), "U64[][]");
+/// assert_eq!(ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()).unwrap(), "[U64, string, number[]]");
 /// ```
 ///
 /// Rust tuples are converted to TypeScript tuples.
@@ -652,15 +1826,33 @@ pub fn ts_doc<W: Write>(buf: &mut W, attrs: &Vec<Attribute>, indent: &str) -> io
 /// ```
 /// # use syn::parse_str;
 /// # use near_syn::ts::ts_type;
-/// assert_eq!(ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()), "[U64, string, number[]]");
+/// assert_eq!(ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()).unwrap(), "[U64, string, number[]]");
+/// ```
+///
+/// Likewise, Rust arrays are converted to fixed-length TypeScript tuples,
+/// unless their length is not a literal.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::ts::ts_type;
+/// assert_eq!(ts_type(&parse_str("[U64; 3]").unwrap()).unwrap(), "[U64, U64, U64]");
+/// assert_eq!(ts_type(&parse_str("[U64; N]").unwrap()).unwrap(), "U64[]");
 /// ```
 ///
-/// ## Panics
+/// ## Errors
 ///
-/// Panics when standard library generics types are used incorrectly.
+/// Returns an `Error::GenericArgs` when standard library generics types are used incorrectly.
 /// For example `Option` or `HashMap<U64>`.
 /// This situation can only happen on Rust source files that were **not** type-checked by `rustc`.
-pub fn ts_type(ty: &Type) -> String {
+///
+/// Returns an `Error::UnsupportedType` when `ty` has no TypeScript equivalent, *e.g.*, raw pointers.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::ts::ts_type;
+/// assert_eq!(ts_type(&parse_str("*const u32").unwrap()).unwrap_err().to_string(), "type not supported: `* const u32`");
+/// ```
+pub fn ts_type(ty: &Type) -> Result<String> {
     #[derive(PartialEq, PartialOrd)]
     enum Assoc {
         Single,
@@ -677,80 +1869,103 @@ pub fn ts_type(ty: &Type) -> String {
             ta.0
         }
     }
-    fn gen_args<'a>(p: &'a syn::TypePath, nargs: usize, name: &str) -> Vec<&'a Type> {
-        if let PathArguments::AngleBracketed(args) = &p.path.segments[0].arguments {
-            if args.args.len() != nargs {
-                panic!(
-                    "{} expects {} generic(s) argument(s), found {}",
-                    name,
-                    nargs,
-                    args.args.len()
-                );
-            }
-            let mut result = Vec::new();
-            for arg in &args.args {
-                if let syn::GenericArgument::Type(tk) = arg {
-                    result.push(tk);
-                } else {
-                    panic!("No type provided for {}", name);
-                }
-            }
-            result
-        } else {
-            panic!("{} used with no generic arguments", name);
-        }
-    }
 
-    fn ts_type_assoc(ty: &Type) -> (String, Assoc) {
-        match ty {
-            Type::Path(p) => match join_path(&p.path).as_str() {
+    fn ts_type_assoc(ty: &Type) -> Result<(String, Assoc)> {
+        Ok(match ty {
+            Type::Path(p) => match type_name(p).as_str() {
                 "bool" => single("boolean"),
-                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "u64" | "i64" => single("number"),
-                "String" => single("string"),
+                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "isize" | "usize" => single("number"),
+                "f32" | "f64" => single("number"),
+                "u64" => single("RawU64"),
+                "i64" => single("RawI64"),
+                "u128" => single("RawU128"),
+                "i128" => single("RawI128"),
+                "String" | "str" | "char" => single("string"),
+                "serde_json::Value" => single("unknown"),
                 "Option" => {
-                    let targs = gen_args(p, 1, "Option");
-                    let ta = ts_type_assoc(&targs[0]);
+                    let targs = type_args(p, 1, "Option")?;
+                    let ta = ts_type_assoc(targs[0])?;
                     (format!("{}|null", use_paren(ta, Assoc::Or)), Assoc::Or)
                 }
-                "Vec" | "HashSet" | "BTreeSet" => {
-                    let targs = gen_args(p, 1, "Vec");
-                    let ta = ts_type_assoc(&targs[0]);
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "IndexSet" => {
+                    let targs = type_args(p, 1, "Vec")?;
+                    let ta = ts_type_assoc(targs[0])?;
                     (format!("{}[]", use_paren(ta, Assoc::Vec)), Assoc::Vec)
                 }
-                "HashMap" | "BTreeMap" => {
-                    let targs = gen_args(p, 2, "HashMap");
-                    let (tks, _) = ts_type_assoc(&targs[0]);
-                    let (tvs, _) = ts_type_assoc(&targs[1]);
+                "HashMap" | "BTreeMap" | "IndexMap" => {
+                    let targs = type_args(p, 2, "HashMap")?;
+                    let (tks, _) = ts_type_assoc(targs[0])?;
+                    let (tvs, _) = ts_type_assoc(targs[1])?;
                     (format!("Record<{}, {}>", tks, tvs), Assoc::Single)
                 }
-                s => single(s),
+                "Result" => {
+                    let targs = type_args(p, 2, "Result")?;
+                    let (tok, _) = ts_type_assoc(targs[0])?;
+                    let (terr, _) = ts_type_assoc(targs[1])?;
+                    (format!("{{ Ok: {} }}|{{ Err: {} }}", tok, terr), Assoc::Or)
+                }
+                "Box" | "Rc" | "Arc" | "Cow" => ts_type_assoc(pointee_type(p)?.unwrap())?,
+                s => match &p.path.segments.last().unwrap().arguments {
+                    PathArguments::AngleBracketed(args) => {
+                        let mut tys = Vec::new();
+                        for arg in &args.args {
+                            match arg {
+                                GenericArgument::Type(ty) => tys.push(ts_type_assoc(ty)?.0),
+                                GenericArgument::Lifetime(_) => {}
+                                _ => return Err(Error::unsupported_type(ty)),
+                            }
+                        }
+                        if tys.is_empty() {
+                            single(s)
+                        } else {
+                            (format!("{}<{}>", s, tys.join(", ")), Assoc::Single)
+                        }
+                    }
+                    _ => single(s),
+                },
             },
-            Type::Paren(paren) => ts_type_assoc(paren.elem.as_ref()),
+            Type::Paren(paren) => ts_type_assoc(paren.elem.as_ref())?,
             Type::Tuple(tuple) => {
                 if tuple.elems.is_empty() {
                     ("void".into(), Assoc::Single)
                 } else {
                     let mut tys = Vec::new();
                     for elem_type in &tuple.elems {
-                        let (t, _) = ts_type_assoc(&elem_type);
+                        let (t, _) = ts_type_assoc(elem_type)?;
                         tys.push(t);
                     }
                     (format!("[{}]", tys.join(", ")), Assoc::Single)
                 }
             }
-            Type::Reference(reference) => ts_type_assoc(&reference.elem),
-            _ => panic!("type not supported: {:?}", ty),
-        }
+            Type::Array(array) => {
+                let ta = ts_type_assoc(&array.elem)?;
+                match array_len(array) {
+                    Some(len) => (format!("[{}]", vec![ta.0; len].join(", ")), Assoc::Single),
+                    None => (format!("{}[]", use_paren(ta, Assoc::Vec)), Assoc::Vec),
+                }
+            }
+            Type::Slice(slice) => {
+                let ta = ts_type_assoc(&slice.elem)?;
+                (format!("{}[]", use_paren(ta, Assoc::Vec)), Assoc::Vec)
+            }
+            Type::Reference(reference) => ts_type_assoc(&reference.elem)?,
+            _ => return Err(Error::unsupported_type(ty)),
+        })
     }
-    ts_type_assoc(ty).0
+    Ok(ts_type_assoc(ty)?.0)
 }
 
 /// Returns the signature of the given Rust `method`.
 /// The resulting TypeScript binding is a valid method definition expected by the NEAR RPC.
 /// Thus, the following conversion are applied:
-/// - Function arguments are packed into a single TypeScript object argument
+/// - Function arguments are packed into a single TypeScript object argument,
+///   callback parameters are excluded since they are not part of the call input
 /// - Return type is wrapped into a `Promise`
 /// - Types are converted using `ts_type`
+/// - Borsh-serialized arguments and results, *i.e.*, `#[serializer(borsh)]` and
+///   `#[result_serializer(borsh)]`, are passed as raw `Uint8Array` bytes
+/// - Change methods take an optional `CallOptions` argument, or `PayableCallOptions` when payable,
+///   so that only `payable` methods can be given an attached deposit
 ///
 /// ### Examples
 ///
@@ -758,52 +1973,97 @@ pub fn ts_type(ty: &Type) -> String {
 /// use syn::parse_str;
 /// use near_syn::ts::ts_sig;
 ///
-/// assert_eq!(ts_sig(&parse_str("fn a() {}").unwrap()), "a(): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn b(x: U128) {}").unwrap()), "b(args: { x: U128 }): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn c(x: U128, y: String) -> Vec<Token> {}").unwrap()), "c(args: { x: U128, y: string }): Promise<Token[]>;");
-/// assert_eq!(ts_sig(&parse_str("fn d(x: U128, y: String, z: Option<U64>) -> Vec<Token> {}").unwrap()), "d(args: { x: U128, y: string, z: U64|null }): Promise<Token[]>;");
-/// assert_eq!(ts_sig(&parse_str("fn e(x: U128) -> () {}").unwrap()), "e(args: { x: U128 }): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn f(paren: (String)) {}").unwrap()), "f(args: { paren: string }): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn get(&self) -> u32 {}").unwrap()), "get(): Promise<number>;");
-/// assert_eq!(ts_sig(&parse_str("fn set(&mut self) {}").unwrap()), "set(gas?: any): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn set_args(&mut self, x: u32) {}").unwrap()), "set_args(args: { x: number }, gas?: any): Promise<void>;");
-/// assert_eq!(ts_sig(&parse_str("fn a() -> Promise {}").unwrap()), "a(): Promise<void>;");
-/// ```
-pub fn ts_sig(method: &ImplItemMethod) -> String {
-    let mut args = Vec::new();
-    for arg in method.sig.inputs.iter() {
-        match arg {
-            syn::FnArg::Typed(pat_type) => {
-                if let syn::Pat::Ident(pat_ident) = pat_type.pat.deref() {
-                    let type_name = ts_type(&pat_type.ty);
-                    let arg_ident = &pat_ident.ident;
-                    args.push(format!("{}: {}", arg_ident, type_name));
-                }
-            }
-            _ => {}
-        }
-    }
+/// assert_eq!(ts_sig(&parse_str("fn a() {}").unwrap()).unwrap(), "a(): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn b(x: U128) {}").unwrap()).unwrap(), "b(args: { x: U128 }): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn c(x: U128, y: String) -> Vec<Token> {}").unwrap()).unwrap(), "c(args: { x: U128, y: string }): Promise<Token[]>;");
+/// assert_eq!(ts_sig(&parse_str("fn d(x: U128, y: String, z: Option<U64>) -> Vec<Token> {}").unwrap()).unwrap(), "d(args: { x: U128, y: string, z: U64|null }): Promise<Token[]>;");
+/// assert_eq!(ts_sig(&parse_str("fn e(x: U128) -> () {}").unwrap()).unwrap(), "e(args: { x: U128 }): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn f(paren: (String)) {}").unwrap()).unwrap(), "f(args: { paren: string }): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn get(&self) -> u32 {}").unwrap()).unwrap(), "get(): Promise<number>;");
+/// assert_eq!(ts_sig(&parse_str("fn set(&mut self) {}").unwrap()).unwrap(), "set(options?: CallOptions): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn set_args(&mut self, x: u32) {}").unwrap()).unwrap(), "set_args(args: { x: number }, options?: CallOptions): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("#[payable] fn buy(&mut self, x: u32) {}").unwrap()).unwrap(), "buy(args: { x: number }, options?: PayableCallOptions): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn a() -> Promise {}").unwrap()).unwrap(), "a(): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("fn on_get(&mut self, key: String, #[callback_unwrap] value: U128) {}").unwrap()).unwrap(), "on_get(args: { key: string }, options?: CallOptions): Promise<void>;");
+/// assert_eq!(ts_sig(&parse_str("#[result_serializer(borsh)] fn get_raw(&self, #[serializer(borsh)] key: Vec<u8>) -> Vec<u8> {}").unwrap()).unwrap(), "get_raw(args: Uint8Array): Promise<Uint8Array>;");
+/// ```
+pub fn ts_sig(method: &ImplItemMethod) -> Result<String> {
+    let args_type = ts_args_type(method)?;
 
-    if method.is_init() {
-        format!("{}: {{ {} }};", method.sig.ident, args.join(", "),)
+    Ok(if method.is_init() {
+        format!(
+            "{}: {};",
+            method.sig.ident,
+            args_type.unwrap_or_else(|| "{  }".to_string())
+        )
     } else {
         let mut args_decl = Vec::new();
-        if args.len() > 0 {
-            args_decl.push(format!("args: {{ {} }}", args.join(", ")));
-        }
-        if method.is_mut() {
-            args_decl.push("gas?: any".into());
+        if let Some(args_type) = args_type {
+            args_decl.push(format!("args: {}", args_type));
         }
         if method.is_payable() {
-            args_decl.push("amount?: any".into());
+            args_decl.push("options?: PayableCallOptions".into());
+        } else if method.is_mut() {
+            args_decl.push("options?: CallOptions".into());
         }
 
         format!(
             "{}({}): Promise<{}>;",
             method.sig.ident,
             args_decl.join(", "),
-            ts_ret_type(&method.sig.output),
+            ts_method_ret_type(method)?,
         )
+    })
+}
+
+/// Returns the TypeScript type of the arguments object of the given Rust `method`,
+/// *i.e.*, an object type with a property for each argument, excluding callback parameters.
+/// Borsh-serialized arguments, *i.e.*, `#[serializer(borsh)]`, are passed as `Uint8Array`.
+/// Returns `None` when `method` takes no arguments.
+///
+/// ```
+/// use syn::parse_str;
+/// use near_syn::ts::ts_args_type;
+///
+/// assert_eq!(ts_args_type(&parse_str("fn get(&self) -> u32 {}").unwrap()).unwrap(), None);
+/// assert_eq!(ts_args_type(&parse_str("fn set(&mut self, x: u32, y: String) {}").unwrap()).unwrap(), Some("{ x: number, y: string }".to_string()));
+/// assert_eq!(ts_args_type(&parse_str("fn set(&mut self, #[serializer(borsh)] x: u32) {}").unwrap()).unwrap(), Some("Uint8Array".to_string()));
+/// ```
+pub fn ts_args_type(method: &ImplItemMethod) -> Result<Option<String>> {
+    let mut args = Vec::new();
+    for pat_type in method.input_args() {
+        if let syn::Pat::Ident(pat_ident) = pat_type.pat.deref() {
+            let type_name = ts_type(&pat_type.ty)?;
+            let arg_ident = &pat_ident.ident;
+            args.push(format!("{}: {}", arg_ident, type_name));
+        }
+    }
+
+    Ok(if args.is_empty() {
+        None
+    } else if method.is_borsh_args() {
+        Some("Uint8Array".to_string())
+    } else {
+        Some(format!("{{ {} }}", args.join(", ")))
+    })
+}
+
+/// Returns the TypeScript return type of the given Rust `method`.
+/// Borsh-serialized results, *i.e.*, `#[result_serializer(borsh)]`, are returned as `Uint8Array`,
+/// otherwise it is given by `ts_ret_type`.
+///
+/// ```
+/// use syn::parse_str;
+/// use near_syn::ts::ts_method_ret_type;
+///
+/// assert_eq!(ts_method_ret_type(&parse_str("fn get(&self) -> Vec<u8> {}").unwrap()).unwrap(), "number[]");
+/// assert_eq!(ts_method_ret_type(&parse_str("#[result_serializer(borsh)] fn get(&self) -> Vec<u8> {}").unwrap()).unwrap(), "Uint8Array");
+/// ```
+pub fn ts_method_ret_type(method: &ImplItemMethod) -> Result<String> {
+    if method.is_borsh_result() {
+        Ok("Uint8Array".to_string())
+    } else {
+        ts_ret_type(&method.sig.output)
     }
 }
 
@@ -811,7 +2071,11 @@ pub fn ts_sig(method: &ImplItemMethod) -> String {
 /// The resulting TypeScript return type is a valid output type expected by the NEAR RPC.
 /// Thus, the following conversion are applied:
 /// - Types are converted using `ts_type`
-/// - Return type of `Promise` or `PromiveOrValue` are mapped to `void`.
+/// - Return type of `Promise` is mapped to `void`.
+/// - Return type of `PromiseOrValue<T>` is mapped to `T`,
+///   since either the value or the result of the promise is returned.
+/// - Return type of `Result<T, E>`, *i.e.*, `#[handle_result]` methods, is mapped to `T`,
+///   since `Err` makes the call fail.
 ///
 /// ### Examples
 ///
@@ -819,18 +2083,29 @@ pub fn ts_sig(method: &ImplItemMethod) -> String {
 /// use syn::parse_str;
 /// use near_syn::ts::ts_ret_type;
 ///
-/// assert_eq!(ts_ret_type(&parse_str(" ").unwrap()), "void");
-/// assert_eq!(ts_ret_type(&parse_str("-> Vec<Token>").unwrap()), "Token[]");
-/// assert_eq!(ts_ret_type(&parse_str("-> Promise<u32>").unwrap()), "void");
-pub fn ts_ret_type(ret_type: &ReturnType) -> String {
-    match ret_type {
-        ReturnType::Default => "void".into(),
-        ReturnType::Type(_, typ) => {
-            let ty = ts_type(typ.deref());
-            match ty.as_str() {
-                "Promise" | "PromiseOrValue" => "void".to_string(),
-                _ => ty,
+/// assert_eq!(ts_ret_type(&parse_str(" ").unwrap()).unwrap(), "void");
+/// assert_eq!(ts_ret_type(&parse_str("-> Vec<Token>").unwrap()).unwrap(), "Token[]");
+/// assert_eq!(ts_ret_type(&parse_str("-> Promise<u32>").unwrap()).unwrap(), "void");
+/// assert_eq!(ts_ret_type(&parse_str("-> PromiseOrValue<u32>").unwrap()).unwrap(), "number");
+/// assert_eq!(ts_ret_type(&parse_str("-> Result<U128, ContractError>").unwrap()).unwrap(), "U128");
+/// ```
+pub fn ts_ret_type(ret_type: &ReturnType) -> Result<String> {
+    fn ts_ret(ty: &Type) -> Result<String> {
+        if let Some((ok, _)) = result_types(ty) {
+            return ts_ret(ok);
+        }
+
+        match ty {
+            ty if is_type(ty, "Promise") => Ok("void".to_string()),
+            Type::Path(p) if is_type(ty, "PromiseOrValue") => {
+                ts_ret(type_args(p, 1, "PromiseOrValue")?[0])
             }
+            ty => ts_type(ty),
         }
     }
+
+    match ret_type {
+        ReturnType::Default => Ok("void".into()),
+        ReturnType::Type(_, typ) => ts_ret(typ),
+    }
 }
@@ -0,0 +1,671 @@
+//! Functions to emit [zod](https://zod.dev) schemas validating the TypeScript bindings at runtime.
+//!
+//! TypeScript types vanish at runtime,
+//! while the results of view calls come from an untrusted RPC.
+//! These schemas follow the same mapping as `ts_type`,
+//! so that parsed values can be trusted to match the emitted bindings.
+
+use crate::{
+    contract::{Contract, NearItem},
+    error::{Error, Location, Result},
+    near_sdk_syn::{
+        array_len, is_type, pointee_type, result_types, serde_enum_repr, serde_fields,
+        serde_variants, type_args, type_name, NearMethod, NearSerde, SerdeEnumRepr,
+    },
+    ts::{ts_prelude_types, ts_prop_name, SdkVersion, TsOptions},
+};
+use std::io::{self, Write};
+use syn::{
+    Attribute, Fields, FieldsNamed, GenericArgument, Generics, ImplItemMethod, ItemEnum,
+    ItemStruct, ItemType, PathArguments, ReturnType, Type, Variant,
+};
+
+/// Exports the schemas of the NEAR Rust SDK types exported by `ts_prelude` for the given `sdk` version.
+/// Numeric types encoded as `string`s, *e.g.*, `U128`, are validated with a digit regex.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_prelude(&mut buf, near_syn::ts::SdkVersion::V4).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"
+/// // Schemas to validate values received from the NEAR RPC.
+///
+/// export const U64Schema = z.string().regex(/^[0-9]+$/);
+/// export const I64Schema = z.string().regex(/^-?[0-9]+$/);
+/// export const U128Schema = z.string().regex(/^[0-9]+$/);
+/// export const I128Schema = z.string().regex(/^-?[0-9]+$/);
+/// export const Base64VecU8Schema = z.string();
+/// export const BalanceSchema = U128Schema;
+/// export const AccountIdSchema = z.string();
+/// export const ValidAccountIdSchema = z.string();
+/// export const GasSchema = z.string().regex(/^[0-9]+$/);
+/// export const PublicKeySchema = z.string();
+/// export const TimestampSchema = z.number().int();
+/// export const DurationSchema = z.number().int();
+/// export const StorageUsageSchema = z.number().int();
+/// export const CryptoHashSchema = z.array(z.number().int()).length(32);
+/// export const Base58CryptoHashSchema = z.string();
+/// export const RawU64Schema = z.number().int();
+/// export const RawI64Schema = z.number().int();
+/// export const RawU128Schema = z.number().int();
+/// export const RawI128Schema = z.number().int();
+///
+/// "#);
+/// ```
+pub fn zod_prelude<W: Write>(buf: &mut W, sdk: SdkVersion) -> io::Result<()> {
+    writeln!(
+        buf,
+        "\n// Schemas to validate values received from the NEAR RPC.\n"
+    )?;
+    for name in ts_prelude_types(sdk) {
+        if let Some(schema) = zod_prelude_schema(name, sdk) {
+            writeln!(buf, "export const {}Schema = {};", name, schema)?;
+        }
+    }
+    writeln!(buf)?;
+
+    Ok(())
+}
+
+/// Exports the schemas of the types overridden in `options.types`, see `ts_type_overrides`.
+/// Their values are not validated, since their TypeScript type is given by the user.
+/// Overridden NEAR Rust SDK types keep the schema exported by `zod_prelude`.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// let options = near_syn::ts::TsOptions {
+///     types: vec![
+///         ("U64".to_string(), "bigint".to_string()),
+///         ("Metadata".to_string(), "Record<string, unknown>".to_string()),
+///     ],
+///     ..Default::default()
+/// };
+/// near_syn::zod::zod_type_overrides(&mut buf, &options).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+///     "export const MetadataSchema: z.ZodType<Metadata> = z.custom<Metadata>();\n\n");
+/// ```
+pub fn zod_type_overrides<W: Write>(buf: &mut W, options: &TsOptions) -> io::Result<()> {
+    for (name, _) in &options.types {
+        if zod_prelude_schema(name, options.sdk).is_none() {
+            writeln!(
+                buf,
+                "export const {0}Schema: z.ZodType<{0}> = z.custom<{0}>();\n",
+                name
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the schema of the NEAR Rust SDK type `name` exported by `ts_prelude`, if any.
+fn zod_prelude_schema(name: &str, sdk: SdkVersion) -> Option<&'static str> {
+    const DIGITS: &str = "z.string().regex(/^[0-9]+$/)";
+    const SIGNED_DIGITS: &str = "z.string().regex(/^-?[0-9]+$/)";
+
+    Some(match name {
+        "U64" | "U128" | "NearToken" => DIGITS,
+        "I64" | "I128" => SIGNED_DIGITS,
+        "Balance" => "U128Schema",
+        "Gas" if sdk == SdkVersion::V3 => "z.number().int()",
+        "Gas" => DIGITS,
+        "PublicKey" if sdk == SdkVersion::V3 => "z.array(z.number().int())",
+        "Base64VecU8" | "AccountId" | "ValidAccountId" | "PublicKey" | "Base58PublicKey"
+        | "Base58CryptoHash" => "z.string()",
+        "Timestamp" | "Duration" | "StorageUsage" => "z.number().int()",
+        "RawU64" | "RawI64" | "RawU128" | "RawI128" => "z.number().int()",
+        "CryptoHash" => "z.array(z.number().int()).length(32)",
+        _ => return None,
+    })
+}
+
+/// Exports the schema of every `struct`, `enum` and type alias in `contract`.
+/// Schemas refer to each other lazily, so they can be emitted in any order.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     struct Token { id: TokenId, owner_id: AccountId }
+///
+///     type TokenId = U64;
+///     "#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_items(&mut buf, &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"export const TokenSchema: z.ZodType<Token> = z.object({
+///     id: z.lazy(() => TokenIdSchema),
+///     owner_id: AccountIdSchema,
+/// });
+///
+/// export const TokenIdSchema: z.ZodType<TokenId> = U64Schema;
+///
+/// "#);
+/// ```
+pub fn zod_items<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    for item in &contract.items {
+        match &**item {
+            NearItem::Impl(_) => Ok(()),
+            NearItem::Struct(item_struct) => zod_struct(buf, item_struct),
+            NearItem::Enum(item_enum) => zod_enum(buf, item_enum),
+            NearItem::Type(item_type) => zod_typedef(buf, item_type),
+        }
+        .map_err(|err| err.in_file(item.file.as_deref()))?;
+    }
+
+    Ok(())
+}
+
+/// Exports the schema of the given `struct`, named after it with the `Schema` suffix.
+/// As in `ts_struct`, the `struct` must derive `Serialize` from `serde`.
+/// Generic `struct`s export a function taking the schemas of their type parameters.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_struct(&mut buf, &syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     struct Page<T> {
+///         items: Vec<T>,
+///         #[serde(default)]
+///         next: Option<U64>,
+///     }"#).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"export function PageSchema<T>(TSchema: z.ZodType<T>): z.ZodType<Page<T>> {
+///     return z.object({
+///         items: z.array(z.lazy(() => TSchema)),
+///         next: U64Schema.nullable().optional(),
+///     });
+/// }
+///
+/// "#);
+/// ```
+pub fn zod_struct<W: Write>(buf: &mut W, item_struct: &ItemStruct) -> Result<()> {
+    if !item_struct.is_serde() {
+        return Ok(());
+    }
+
+    let schema = match &item_struct.fields {
+        Fields::Named(fields) => zod_object(Vec::new(), fields, &item_struct.attrs, true)?,
+        Fields::Unnamed(_) => zod_fields(&item_struct.fields, &item_struct.attrs)?,
+        Fields::Unit => {
+            return Err(Error::UnitStruct {
+                name: item_struct.ident.to_string(),
+                location: Location::of(item_struct),
+            })
+        }
+    };
+    zod_export(
+        buf,
+        &item_struct.ident.to_string(),
+        &item_struct.generics,
+        &schema,
+    )
+}
+
+/// Exports the schema of the given `enum`, following its `serde` representation as `ts_enum` does.
+/// Enums with only unit variants are validated against the members of the emitted TypeScript `enum`,
+/// which are initialized to the names of their variants in JSON.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_enum(&mut buf, &syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     enum Action {
+///         Transfer { to: AccountId, amount: U128 },
+///         Burn(U128),
+///         Stop,
+///     }"#).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"export const ActionSchema: z.ZodType<Action> = z.union([
+///     z.object({ Transfer: z.object({ to: AccountIdSchema, amount: U128Schema }) }),
+///     z.object({ Burn: U128Schema }),
+///     z.literal("Stop"),
+/// ]);
+///
+/// "#);
+///
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_enum(&mut buf, &syn::parse_str(r#"
+///     #[derive(Serialize)]
+///     #[serde(rename_all = "snake_case")]
+///     enum Status { Started, NotFound, #[serde(rename = "done")] Finished }"#).unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+///     "export const StatusSchema: z.ZodType<Status> = z.nativeEnum(Status);\n\n");
+/// ```
+pub fn zod_enum<W: Write>(buf: &mut W, item_enum: &ItemEnum) -> Result<()> {
+    if !item_enum.is_serde() {
+        return Ok(());
+    }
+
+    let repr = serde_enum_repr(&item_enum.attrs);
+    let variants = serde_variants(&item_enum.variants, &item_enum.attrs);
+    let is_plain = variants
+        .iter()
+        .all(|(variant, _)| variant.fields == Fields::Unit);
+
+    let schema = if is_plain && repr == SerdeEnumRepr::External {
+        format!("z.nativeEnum({})", item_enum.ident)
+    } else {
+        let mut members = Vec::new();
+        for (variant, name) in &variants {
+            members.push(zod_variant(variant, name, &repr)?);
+        }
+        match members.len() {
+            0 => "z.never()".to_string(),
+            1 => members.remove(0),
+            _ => format!(
+                "z.union([\n{}])",
+                members
+                    .iter()
+                    .map(|member| format!("    {},\n", member))
+                    .collect::<String>()
+            ),
+        }
+    };
+    zod_export(
+        buf,
+        &item_enum.ident.to_string(),
+        &item_enum.generics,
+        &schema,
+    )
+}
+
+/// Exports the schema of the given type alias.
+///
+/// ```
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_typedef(&mut buf, &syn::parse_str("type Balances = HashMap<AccountId, U128>;").unwrap()).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+///     "export const BalancesSchema: z.ZodType<Balances> = z.record(AccountIdSchema, U128Schema);\n\n");
+/// ```
+pub fn zod_typedef<W: Write>(buf: &mut W, item_type: &ItemType) -> Result<()> {
+    zod_export(
+        buf,
+        &item_type.ident.to_string(),
+        &item_type.generics,
+        &zod_type(&item_type.ty)?,
+    )
+}
+
+/// Exports `schema` as the schema of the TypeScript type `name`.
+/// When `generics` has type parameters, a function taking their schemas is exported instead.
+fn zod_export<W: Write>(buf: &mut W, name: &str, generics: &Generics, schema: &str) -> Result<()> {
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect::<Vec<String>>();
+
+    if params.is_empty() {
+        writeln!(
+            buf,
+            "export const {0}Schema: z.ZodType<{0}> = {1};\n",
+            name, schema
+        )?;
+    } else {
+        writeln!(
+            buf,
+            "export function {}Schema<{}>({}): z.ZodType<{}<{}>> {{",
+            name,
+            params.join(", "),
+            params
+                .iter()
+                .map(|param| format!("{0}Schema: z.ZodType<{0}>", param))
+                .collect::<Vec<String>>()
+                .join(", "),
+            name,
+            params.join(", ")
+        )?;
+        writeln!(buf, "    return {};", schema.replace('\n', "\n    "))?;
+        writeln!(buf, "}}\n")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the schema of the keys of a map with key type `ty`.
+/// JSON object keys are always strings, thus integer keys are validated with a digit regex.
+fn zod_key(ty: &Type) -> Result<String> {
+    let name = match ty {
+        Type::Path(p) => type_name(p),
+        _ => return zod_type(ty),
+    };
+    Ok(match name.as_str() {
+        "u8" | "u16" | "u32" | "usize" | "u64" | "u128" => "z.string().regex(/^[0-9]+$/)",
+        "i8" | "i16" | "i32" | "isize" | "i64" | "i128" => "z.string().regex(/^-?[0-9]+$/)",
+        _ => return zod_type(ty),
+    }
+    .to_string())
+}
+
+/// Returns the schema of an `enum` `variant` named `name` according to the `serde` representation `repr`.
+fn zod_variant(variant: &Variant, name: &str, repr: &SerdeEnumRepr) -> Result<String> {
+    Ok(match repr {
+        SerdeEnumRepr::External => match &variant.fields {
+            Fields::Unit => format!("z.literal({:?})", name),
+            fields => format!(
+                "z.object({{ {}: {} }})",
+                ts_prop_name(name),
+                zod_fields(fields, &variant.attrs)?
+            ),
+        },
+        SerdeEnumRepr::Internal { tag } => {
+            let tag = format!("{}: z.literal({:?})", ts_prop_name(tag), name);
+            match &variant.fields {
+                Fields::Unit => format!("z.object({{ {} }})", tag),
+                Fields::Named(fields) => zod_object(vec![tag], fields, &variant.attrs, false)?,
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => format!(
+                    "z.object({{ {} }}).and({})",
+                    tag,
+                    zod_type(&fields.unnamed[0].ty)?
+                ),
+                Fields::Unnamed(_) => {
+                    return Err(Error::TupleVariant {
+                        name: name.to_string(),
+                        location: Location::of(variant),
+                    })
+                }
+            }
+        }
+        SerdeEnumRepr::Adjacent { tag, content } => match &variant.fields {
+            Fields::Unit => format!(
+                "z.object({{ {}: z.literal({:?}) }})",
+                ts_prop_name(tag),
+                name
+            ),
+            fields => format!(
+                "z.object({{ {}: z.literal({:?}), {}: {} }})",
+                ts_prop_name(tag),
+                name,
+                ts_prop_name(content),
+                zod_fields(fields, &variant.attrs)?
+            ),
+        },
+        SerdeEnumRepr::Untagged => match &variant.fields {
+            Fields::Unit => "z.null()".to_string(),
+            fields => zod_fields(fields, &variant.attrs)?,
+        },
+    })
+}
+
+/// Returns the schema of `fields` with attributes `attrs`, see `ts_fields`.
+fn zod_fields(fields: &Fields, attrs: &[Attribute]) -> Result<String> {
+    Ok(match fields {
+        Fields::Named(fields) => zod_object(Vec::new(), fields, attrs, false)?,
+        Fields::Unnamed(fields) => {
+            let schemas = fields
+                .unnamed
+                .iter()
+                .map(|field| zod_type(&field.ty))
+                .collect::<Result<Vec<String>>>()?;
+            if schemas.len() == 1 {
+                schemas[0].clone()
+            } else {
+                format!("z.tuple([{}])", schemas.join(", "))
+            }
+        }
+        Fields::Unit => "z.null()".to_string(),
+    })
+}
+
+/// Returns the `z.object` schema of named `fields` after the given `members`,
+/// one member per line when `multiline` is set.
+/// Optional fields are marked with `.optional()` and flattened fields are intersected with `.and`.
+fn zod_object(
+    mut members: Vec<String>,
+    fields: &FieldsNamed,
+    attrs: &[Attribute],
+    multiline: bool,
+) -> Result<String> {
+    let mut flattened = Vec::new();
+    for field in serde_fields(fields, attrs) {
+        let schema = zod_type(&field.field.ty)?;
+        if field.flatten {
+            flattened.push(schema);
+        } else {
+            let optional = if field.optional { ".optional()" } else { "" };
+            members.push(format!(
+                "{}: {}{}",
+                ts_prop_name(&field.name),
+                schema,
+                optional
+            ));
+        }
+    }
+
+    let mut object = if members.is_empty() {
+        "z.object({})".to_string()
+    } else if multiline {
+        format!(
+            "z.object({{\n{}}})",
+            members
+                .iter()
+                .map(|member| format!("    {},\n", member))
+                .collect::<String>()
+        )
+    } else {
+        format!("z.object({{ {} }})", members.join(", "))
+    };
+    for schema in flattened {
+        object.push_str(&format!(".and({})", schema));
+    }
+
+    Ok(object)
+}
+
+/// Exports the `{Name}ResultSchemas` object with the schema of the result of each method returning a value,
+/// and the `parse{Name}Result` function validating the result of a call, *e.g.*, of a view method.
+/// Borsh-serialized results are validated as `Uint8Array`s.
+///
+/// ```
+/// let mut contract = near_syn::contract::Contract::new();
+/// contract.push_ast(syn::parse_str(r#"
+///     #[near_bindgen]
+///     impl Contract {
+///         pub fn get(&self) -> U128 { 42 }
+///         pub fn set(&mut self, value: u32) {}
+///     }"#).unwrap()).unwrap();
+/// let mut buf = Vec::new();
+/// near_syn::zod::zod_result_schemas(&mut buf, &contract).unwrap();
+/// assert_eq!(String::from_utf8_lossy(&buf),
+/// r#"export const ContractResultSchemas = {
+///     get: U128Schema,
+/// };
+///
+/// export function parseContractResult<M extends keyof typeof ContractResultSchemas>(method: M, result: unknown): z.infer<typeof ContractResultSchemas[M]> {
+///     return ContractResultSchemas[method].parse(result) as z.infer<typeof ContractResultSchemas[M]>;
+/// }
+/// "#);
+/// ```
+pub fn zod_result_schemas<W: Write>(buf: &mut W, contract: &Contract) -> Result<()> {
+    let name = contract.name.clone().unwrap_or_default();
+
+    writeln!(buf, "export const {}ResultSchemas = {{", name)?;
+    for method_name in contract.view_methods.iter().chain(&contract.change_methods) {
+        if let Some((method, _, file)) = contract.methods.get(method_name) {
+            let schema = zod_method_ret_type(method).map_err(|err| err.in_file(file.as_deref()))?;
+            if let Some(schema) = schema {
+                writeln!(buf, "    {}: {},", method_name, schema)?;
+            }
+        }
+    }
+    writeln!(buf, "}};\n")?;
+
+    writeln!(
+        buf,
+        "export function parse{0}Result<M extends keyof typeof {0}ResultSchemas>(method: M, result: unknown): z.infer<typeof {0}ResultSchemas[M]> {{",
+        name
+    )?;
+    writeln!(
+        buf,
+        "    return {0}ResultSchemas[method].parse(result) as z.infer<typeof {0}ResultSchemas[M]>;",
+        name
+    )?;
+    writeln!(buf, "}}")?;
+
+    Ok(())
+}
+
+/// Returns the schema of the value returned by `method` to the caller, if any, see `ts_method_ret_type`.
+///
+/// ```
+/// use syn::parse_str;
+/// use near_syn::zod::zod_method_ret_type;
+///
+/// assert_eq!(zod_method_ret_type(&parse_str("fn f(&self) {}").unwrap()).unwrap(), None);
+/// assert_eq!(zod_method_ret_type(&parse_str("fn f(&self) -> Promise {}").unwrap()).unwrap(), None);
+/// assert_eq!(zod_method_ret_type(&parse_str("fn f(&self) -> PromiseOrValue<bool> {}").unwrap()).unwrap(), Some("z.boolean()".to_string()));
+/// assert_eq!(zod_method_ret_type(&parse_str("fn f(&self) -> Option<Token> {}").unwrap()).unwrap(), Some("z.lazy(() => TokenSchema).nullable()".to_string()));
+/// assert_eq!(zod_method_ret_type(&parse_str("#[handle_result] fn f(&self) -> Result<u32, String> {}").unwrap()).unwrap(), Some("z.number().int()".to_string()));
+/// assert_eq!(zod_method_ret_type(&parse_str("#[result_serializer(borsh)] fn f(&self) -> Vec<u8> {}").unwrap()).unwrap(), Some("z.instanceof(Uint8Array)".to_string()));
+/// ```
+pub fn zod_method_ret_type(method: &ImplItemMethod) -> Result<Option<String>> {
+    fn zod_ret(ty: &Type) -> Result<Option<String>> {
+        if let Some((ok, _)) = result_types(ty) {
+            return zod_ret(ok);
+        }
+
+        match ty {
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(None),
+            ty if is_type(ty, "Promise") => Ok(None),
+            Type::Path(p) if is_type(ty, "PromiseOrValue") => {
+                zod_ret(type_args(p, 1, "PromiseOrValue")?[0])
+            }
+            ty => zod_type(ty).map(Some),
+        }
+    }
+
+    match &method.sig.output {
+        ReturnType::Default => Ok(None),
+        ReturnType::Type(_, ty) => {
+            let schema = zod_ret(ty)?;
+            if schema.is_some() && method.is_borsh_result() {
+                Ok(Some("z.instanceof(Uint8Array)".to_string()))
+            } else {
+                Ok(schema)
+            }
+        }
+    }
+}
+
+/// Returns the zod schema validating the TypeScript type `ts_type` translates `ty` to.
+/// Schemas of user-defined types are referenced lazily by name with the `Schema` suffix.
+///
+/// ```
+/// use syn::parse_str;
+/// use near_syn::zod::zod_type;
+///
+/// assert_eq!(zod_type(&parse_str("bool").unwrap()).unwrap(), "z.boolean()");
+/// assert_eq!(zod_type(&parse_str("u32").unwrap()).unwrap(), "z.number().int()");
+/// assert_eq!(zod_type(&parse_str("f64").unwrap()).unwrap(), "z.number()");
+/// assert_eq!(zod_type(&parse_str("char").unwrap()).unwrap(), "z.string().length(1)");
+/// assert_eq!(zod_type(&parse_str("serde_json::Value").unwrap()).unwrap(), "z.unknown()");
+/// assert_eq!(zod_type(&parse_str("Box<[u8; 2]>").unwrap()).unwrap(), "z.tuple([z.number().int(), z.number().int()])");
+/// assert_eq!(zod_type(&parse_str("&String").unwrap()).unwrap(), "z.string()");
+/// assert_eq!(zod_type(&parse_str("U128").unwrap()).unwrap(), "U128Schema");
+/// assert_eq!(zod_type(&parse_str("Option<U64>").unwrap()).unwrap(), "U64Schema.nullable()");
+/// assert_eq!(zod_type(&parse_str("Vec<Token>").unwrap()).unwrap(), "z.array(z.lazy(() => TokenSchema))");
+/// assert_eq!(zod_type(&parse_str("HashMap<AccountId, U128>").unwrap()).unwrap(), "z.record(AccountIdSchema, U128Schema)");
+/// assert_eq!(zod_type(&parse_str("HashMap<u32, String>").unwrap()).unwrap(), "z.record(z.string().regex(/^[0-9]+$/), z.string())");
+/// assert_eq!(zod_type(&parse_str("BTreeMap<i64, u8>").unwrap()).unwrap(), "z.record(z.string().regex(/^-?[0-9]+$/), z.number().int())");
+/// assert_eq!(zod_type(&parse_str("(U64, String)").unwrap()).unwrap(), "z.tuple([U64Schema, z.string()])");
+/// assert_eq!(zod_type(&parse_str("Page<Token>").unwrap()).unwrap(), "z.lazy(() => PageSchema(z.lazy(() => TokenSchema)))");
+/// assert_eq!(zod_type(&parse_str("Result<u8, String>").unwrap()).unwrap(), "z.union([z.object({ Ok: z.number().int() }), z.object({ Err: z.string() })])");
+/// ```
+///
+/// Raw 64 and 128 bits integers refer to the `RawU64Schema`, `RawI64Schema`, `RawU128Schema` and `RawI128Schema` schemas,
+/// matching the aliases returned by `ts_type`.
+/// They accept any integer `number`, since precision lost above `Number.MAX_SAFE_INTEGER` cannot be detected after parsing JSON.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::zod::zod_type;
+/// assert_eq!(zod_type(&parse_str("u64").unwrap()).unwrap(), "RawU64Schema");
+/// assert_eq!(zod_type(&parse_str("i128").unwrap()).unwrap(), "RawI128Schema");
+/// assert_eq!(zod_type(&parse_str("Vec<u128>").unwrap()).unwrap(), "z.array(RawU128Schema)");
+/// ```
+///
+/// ## Errors
+///
+/// The same errors as `ts_type` are returned.
+///
+/// ```
+/// # use syn::parse_str;
+/// # use near_syn::zod::zod_type;
+/// assert_eq!(zod_type(&parse_str("*const u32").unwrap()).unwrap_err().to_string(), "type not supported: `* const u32`");
+/// ```
+pub fn zod_type(ty: &Type) -> Result<String> {
+    Ok(match ty {
+        Type::Path(p) => match type_name(p).as_str() {
+            "bool" => "z.boolean()".to_string(),
+            "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "isize" | "usize" => {
+                "z.number().int()".to_string()
+            }
+            "u64" => "RawU64Schema".to_string(),
+            "i64" => "RawI64Schema".to_string(),
+            "u128" => "RawU128Schema".to_string(),
+            "i128" => "RawI128Schema".to_string(),
+            "f32" | "f64" => "z.number()".to_string(),
+            "String" | "str" => "z.string()".to_string(),
+            "char" => "z.string().length(1)".to_string(),
+            "serde_json::Value" => "z.unknown()".to_string(),
+            "Option" => format!("{}.nullable()", zod_type(type_args(p, 1, "Option")?[0])?),
+            "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "IndexSet" => {
+                format!("z.array({})", zod_type(type_args(p, 1, "Vec")?[0])?)
+            }
+            "HashMap" | "BTreeMap" | "IndexMap" => {
+                let targs = type_args(p, 2, "HashMap")?;
+                format!("z.record({}, {})", zod_key(targs[0])?, zod_type(targs[1])?)
+            }
+            "Result" => {
+                let targs = type_args(p, 2, "Result")?;
+                format!(
+                    "z.union([z.object({{ Ok: {} }}), z.object({{ Err: {} }})])",
+                    zod_type(targs[0])?,
+                    zod_type(targs[1])?
+                )
+            }
+            "Box" | "Rc" | "Arc" | "Cow" => zod_type(pointee_type(p)?.unwrap())?,
+            s if zod_prelude_schema(s, SdkVersion::default()).is_some() => format!("{}Schema", s),
+            s => {
+                let mut schemas = Vec::new();
+                if let PathArguments::AngleBracketed(args) =
+                    &p.path.segments.last().unwrap().arguments
+                {
+                    for arg in &args.args {
+                        match arg {
+                            GenericArgument::Type(ty) => schemas.push(zod_type(ty)?),
+                            GenericArgument::Lifetime(_) => {}
+                            _ => return Err(Error::unsupported_type(ty)),
+                        }
+                    }
+                }
+                if schemas.is_empty() {
+                    format!("z.lazy(() => {}Schema)", s)
+                } else {
+                    format!("z.lazy(() => {}Schema({}))", s, schemas.join(", "))
+                }
+            }
+        },
+        Type::Paren(paren) => zod_type(&paren.elem)?,
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "z.void()".to_string(),
+        Type::Tuple(tuple) => format!(
+            "z.tuple([{}])",
+            tuple
+                .elems
+                .iter()
+                .map(zod_type)
+                .collect::<Result<Vec<String>>>()?
+                .join(", ")
+        ),
+        Type::Array(array) => {
+            let schema = zod_type(&array.elem)?;
+            match array_len(array) {
+                Some(len) => format!("z.tuple([{}])", vec![schema; len].join(", ")),
+                None => format!("z.array({})", schema),
+            }
+        }
+        Type::Slice(slice) => format!("z.array({})", zod_type(&slice.elem)?),
+        Type::Reference(reference) => zod_type(&reference.elem)?,
+        _ => return Err(Error::unsupported_type(ty)),
+    })
+}
@@ -0,0 +1,656 @@
+use serde_json::Value;
+
+/// Collects the `$ref`s in `schema` which do not point to any definition in `defs`.
+fn dangling_refs(schema: &Value, prefix: &str, defs: &Value) -> Vec<String> {
+    match schema {
+        Value::Object(map) => map
+            .iter()
+            .flat_map(|(key, value)| match (key.as_str(), value) {
+                ("$ref", Value::String(r)) => match r.strip_prefix(prefix) {
+                    Some(name) if defs.get(name).is_some() => vec![],
+                    _ => vec![r.clone()],
+                },
+                _ => dangling_refs(value, prefix, defs),
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .flat_map(|item| dangling_refs(item, prefix, defs))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+mod abi_functions {
+    use near_syn::{abi::abi_functions, contract::Contract};
+    use quote::quote;
+    use serde_json::json;
+    use syn::{parse2, File};
+
+    #[test]
+    fn it_should_list_init_view_and_change_methods() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                pub fn set(&mut self) {}
+                pub fn get(&self) -> bool { true }
+                #[init]
+                pub fn new() -> Self { Self {} }
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![
+                json!({ "name": "new", "kind": "call", "modifiers": ["init"] }),
+                json!({
+                    "name": "get",
+                    "kind": "view",
+                    "result": { "serialization_type": "json", "type_schema": { "type": "boolean" } },
+                }),
+                json!({ "name": "set", "kind": "call" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_ok_type_of_handle_result_methods() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                #[handle_result]
+                pub fn get(&self) -> Result<bool, ContractError> { Ok(true) }
+                #[handle_result]
+                pub fn transfer(&mut self) -> Result<Promise, &'static str> { Err("") }
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![
+                json!({
+                    "name": "get",
+                    "kind": "view",
+                    "result": { "serialization_type": "json", "type_schema": { "type": "boolean" } },
+                }),
+                json!({ "name": "transfer", "kind": "call" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_return_the_value_type_of_promise_or_value_methods() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                pub fn get(&self) -> PromiseOrValue<bool> { PromiseOrValue::Value(true) }
+                pub fn transfer(&mut self) -> Promise { }
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![
+                json!({
+                    "name": "get",
+                    "kind": "view",
+                    "result": { "serialization_type": "json", "type_schema": { "type": "boolean" } },
+                }),
+                json!({ "name": "transfer", "kind": "call" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_list_callbacks_with_their_promise_results() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                pub fn transfer(&mut self, amount: u32) -> Promise { }
+                #[private]
+                pub fn on_transfer(
+                    &mut self,
+                    amount: u32,
+                    #[callback_unwrap] ok: bool,
+                    #[callback_result] memo: Result<String, PromiseError>,
+                ) {}
+                #[private]
+                pub fn on_all(&mut self, #[callback_vec] results: Vec<u8>) {}
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        assert_eq!(contract.callbacks, vec!["on_transfer", "on_all"]);
+        assert_eq!(contract.change_methods, vec!["transfer"]);
+
+        let amount = json!({ "name": "amount", "type_schema": { "type": "integer", "format": "uint32", "minimum": 0 } });
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![
+                json!({
+                    "name": "transfer",
+                    "kind": "call",
+                    "params": { "serialization_type": "json", "args": [amount] },
+                }),
+                json!({
+                    "name": "on_transfer",
+                    "kind": "call",
+                    "modifiers": ["private"],
+                    "params": { "serialization_type": "json", "args": [amount] },
+                    "callbacks": [
+                        { "serialization_type": "json", "type_schema": { "type": "boolean" } },
+                        { "serialization_type": "json", "type_schema": { "type": "string" } },
+                    ],
+                }),
+                json!({
+                    "name": "on_all",
+                    "kind": "call",
+                    "modifiers": ["private"],
+                    "callbacks_vec": {
+                        "serialization_type": "json",
+                        "type_schema": { "type": "integer", "format": "uint8", "minimum": 0 },
+                    },
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_describe_borsh_serialized_args_and_results() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                #[result_serializer(borsh)]
+                pub fn raw(&self, #[serializer(borsh)] key: Vec<u8>) -> Vec<u8> { key }
+                pub fn get(&self, key: String) -> u8 { 0 }
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![
+                json!({
+                    "name": "raw",
+                    "kind": "view",
+                    "params": { "serialization_type": "borsh", "args": [{ "name": "key" }] },
+                    "result": { "serialization_type": "borsh" },
+                }),
+                json!({
+                    "name": "get",
+                    "kind": "view",
+                    "params": {
+                        "serialization_type": "json",
+                        "args": [{ "name": "key", "type_schema": { "type": "string" } }],
+                    },
+                    "result": {
+                        "serialization_type": "json",
+                        "type_schema": { "type": "integer", "format": "uint8", "minimum": 0 },
+                    },
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_merge_trait_and_impl_doc_comments() {
+        let ast: File = parse2(quote! {
+            trait IContract {
+                /// doc for IContract::get
+                fn get(&self) -> Promise;
+            }
+
+            #[near_bindgen]
+            impl IContract for Contract {
+                /// doc in Contract::get
+                fn get(&self) -> Promise { }
+            }
+        })
+        .unwrap();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        assert_eq!(
+            abi_functions(&contract).unwrap(),
+            vec![json!({
+                "name": "get",
+                "doc": "doc in Contract::get\ndoc for IContract::get",
+                "kind": "view",
+            })]
+        );
+    }
+}
+
+mod schema_type {
+    use near_syn::schema::schema_type;
+    use serde_json::json;
+    use syn::parse_str;
+
+    #[test]
+    fn it_should_convert_rust_unit_type() {
+        assert_eq!(
+            schema_type(&parse_str("()").unwrap()).unwrap(),
+            json!({ "type": "null" })
+        );
+    }
+
+    #[test]
+    fn it_should_convert_rust_standard_and_collection_types() {
+        assert_eq!(
+            schema_type(&parse_str("Option<Token>").unwrap()).unwrap(),
+            json!({ "anyOf": [{ "$ref": "#/definitions/Token" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            schema_type(&parse_str("Vec<bool>").unwrap()).unwrap(),
+            json!({ "type": "array", "items": { "type": "boolean" } })
+        );
+        assert_eq!(
+            schema_type(&parse_str("BTreeSet<AccountId>").unwrap()).unwrap(),
+            json!({
+                "type": "array",
+                "items": schema_type(&parse_str("AccountId").unwrap()).unwrap(),
+                "uniqueItems": true,
+            })
+        );
+        assert_eq!(
+            schema_type(&parse_str("BTreeMap<AccountId, u8>").unwrap()).unwrap(),
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "integer", "format": "uint8", "minimum": 0 },
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_convert_near_sdk_json_types_to_strings_with_patterns() {
+        assert_eq!(
+            schema_type(&parse_str("U64").unwrap()).unwrap(),
+            json!({ "type": "string", "pattern": "^[0-9]+$" })
+        );
+        assert_eq!(
+            schema_type(&parse_str("I128").unwrap()).unwrap(),
+            json!({ "type": "string", "pattern": "^-?[0-9]+$" })
+        );
+
+        assert_eq!(
+            schema_type(&parse_str("ValidAccountId").unwrap()).unwrap(),
+            json!({
+                "type": "string",
+                "pattern": near_syn::schema::ACCOUNT_ID_PATTERN,
+                "minLength": 2,
+                "maxLength": 64,
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_convert_near_sdk_types_inline() {
+        assert_eq!(
+            schema_type(&parse_str("near_sdk::Gas").unwrap()).unwrap(),
+            json!({ "type": "string", "pattern": "^[0-9]+$" })
+        );
+        assert_eq!(
+            schema_type(&parse_str("Timestamp").unwrap()).unwrap(),
+            json!({ "type": "integer", "format": "uint64", "minimum": 0 })
+        );
+        assert_eq!(
+            schema_type(&parse_str("CryptoHash").unwrap()).unwrap(),
+            json!({
+                "type": "array",
+                "items": { "type": "integer", "format": "uint8", "minimum": 0 },
+                "minItems": 32,
+                "maxItems": 32,
+            })
+        );
+        assert_eq!(
+            schema_type(&parse_str("PublicKey").unwrap()).unwrap(),
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_not_supported_type() {
+        let err = schema_type(&parse_str("*const u32").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "type not supported: `* const u32`");
+    }
+}
+
+mod schema_enum {
+    use near_syn::schema::schema_enum;
+    use serde_json::json;
+    use syn::parse_str;
+
+    #[test]
+    fn it_should_follow_internally_tagged_representation() {
+        let schema = schema_enum(
+            &parse_str(
+                r#"
+                #[derive(Serialize)]
+                #[serde(tag = "type")]
+                enum E { A { x: u8 }, B(Token), C }"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let tag = |name| json!({ "type": "string", "enum": [name] });
+        assert_eq!(
+            schema,
+            json!({ "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["type", "x"],
+                    "properties": {
+                        "type": tag("A"),
+                        "x": { "type": "integer", "format": "uint8", "minimum": 0 },
+                    },
+                },
+                { "allOf": [
+                    { "type": "object", "required": ["type"], "properties": { "type": tag("B") } },
+                    { "$ref": "#/definitions/Token" },
+                ]},
+                { "type": "object", "required": ["type"], "properties": { "type": tag("C") } },
+            ]})
+        );
+    }
+
+    #[test]
+    fn it_should_follow_adjacently_tagged_and_untagged_representations() {
+        let schema = schema_enum(
+            &parse_str(
+                r#"
+                #[derive(Serialize)]
+                #[serde(tag = "t", content = "c")]
+                enum E { A(bool), B }"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            schema,
+            json!({ "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["t", "c"],
+                    "properties": { "t": { "type": "string", "enum": ["A"] }, "c": { "type": "boolean" } },
+                },
+                { "type": "object", "required": ["t"], "properties": { "t": { "type": "string", "enum": ["B"] } } },
+            ]})
+        );
+
+        let schema = schema_enum(
+            &parse_str("#[derive(Serialize)] #[serde(untagged)] enum E { A(bool), B }").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            schema,
+            json!({ "oneOf": [{ "type": "boolean" }, { "type": "null" }] })
+        );
+    }
+
+    #[test]
+    fn it_should_fail_on_internally_tagged_tuple_variants() {
+        let err = schema_enum(
+            &parse_str("#[derive(Serialize)] #[serde(tag = \"t\")] enum E { A(u8, u8) }").unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tuple variant not supported in internally tagged enum: `A`"
+        );
+    }
+}
+
+mod schema_struct {
+    use near_syn::schema::schema_struct;
+    use serde_json::json;
+    use syn::parse_str;
+
+    #[test]
+    fn it_should_honour_serde_field_attributes() {
+        let schema = schema_struct(
+            &parse_str(
+                r#"
+                #[derive(Serialize)]
+                #[serde(rename_all = "camelCase")]
+                struct A {
+                    owner_id: String,
+                    #[serde(default)]
+                    total_supply: U128,
+                    #[serde(skip)]
+                    cache: u32,
+                    #[serde(flatten)]
+                    metadata: Metadata,
+                }"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            schema,
+            json!({ "allOf": [
+                {
+                    "type": "object",
+                    "required": ["ownerId"],
+                    "properties": {
+                        "ownerId": { "type": "string" },
+                        "totalSupply": { "type": "string", "pattern": "^[0-9]+$" },
+                    },
+                },
+                { "$ref": "#/definitions/Metadata" },
+            ]})
+        );
+    }
+}
+
+mod schema_root {
+    use near_syn::{contract::Contract, schema::schema_root};
+    use serde_json::json;
+    use syn::parse_str;
+
+    #[test]
+    fn it_should_reference_types_from_defs() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse_str(
+                    r#"
+                    #[derive(Serialize)]
+                    struct Token { next: Option<Token> }
+
+                    #[near_bindgen]
+                    impl Contract {
+                        pub fn set(&mut self, tokens: Vec<Token>) {}
+                    }"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let schema = schema_root(&contract).unwrap();
+        assert_eq!(
+            schema["$defs"]["Token"]["properties"]["next"],
+            json!({ "anyOf": [{ "$ref": "#/$defs/Token" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            schema["$defs"]["set_args"],
+            json!({
+                "type": "object",
+                "required": ["tokens"],
+                "properties": {
+                    "tokens": { "type": "array", "items": { "$ref": "#/$defs/Token" } },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_describe_tuples_with_prefix_items() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse_str(
+                    r#"
+                    #[near_bindgen]
+                    impl Contract {
+                        pub fn set(&mut self, pair: (String, u8), triple: [bool; 3]) {}
+                    }"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let schema = schema_root(&contract).unwrap();
+        let properties = &schema["$defs"]["set_args"]["properties"];
+        let uint8 = json!({ "type": "integer", "format": "uint8", "minimum": 0 });
+        assert_eq!(
+            properties["pair"],
+            json!({
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, uint8],
+                "items": false,
+                "minItems": 2,
+                "maxItems": 2,
+            })
+        );
+        assert_eq!(
+            properties["triple"],
+            json!({
+                "type": "array",
+                "prefixItems": [{ "type": "boolean" }, { "type": "boolean" }, { "type": "boolean" }],
+                "items": false,
+                "minItems": 3,
+                "maxItems": 3,
+            })
+        );
+
+        // The ABI keeps the draft-07 form.
+        let abi = near_syn::abi::abi_root(&contract).unwrap();
+        assert_eq!(
+            abi["body"]["functions"][0]["params"]["args"][0]["type_schema"]["items"],
+            json!([{ "type": "string" }, uint8])
+        );
+    }
+
+    #[test]
+    fn it_should_not_leave_dangling_refs_to_near_sdk_types() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse_str(
+                    r#"
+                    #[derive(Serialize)]
+                    struct Stamp { at: Timestamp, hash: CryptoHash, key: PublicKey }
+
+                    #[near_bindgen]
+                    impl Contract {
+                        pub fn stamp(&mut self, gas: Gas, deposit: NearToken, stamp: Stamp) {}
+                        pub fn last(&self) -> Option<Stamp> {}
+                    }"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let schema = schema_root(&contract).unwrap();
+        assert_eq!(
+            super::dangling_refs(&schema, "#/$defs/", &schema["$defs"]),
+            Vec::<String>::new()
+        );
+    }
+}
+
+mod abi_root {
+    use near_syn::{abi::abi_root, contract::Contract};
+    use serde_json::json;
+    use syn::parse_str;
+
+    #[test]
+    fn it_should_define_each_instantiation_of_generic_types() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse_str(
+                    r#"
+                    #[derive(Serialize)]
+                    struct Info { id: u32 }
+
+                    #[derive(Serialize)]
+                    struct Page<T> { items: Vec<T>, first: Option<Wrapper<T>> }
+
+                    #[derive(Serialize)]
+                    struct Wrapper<T>(T);
+
+                    #[near_bindgen]
+                    impl Contract {
+                        pub fn infos(&self) -> Page<Info> {}
+                    }"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let abi = abi_root(&contract).unwrap();
+        assert_eq!(
+            abi["body"]["functions"][0]["result"]["type_schema"],
+            json!({ "$ref": "#/definitions/Page_for_Info" })
+        );
+        let definitions = &abi["body"]["root_schema"]["definitions"];
+        assert_eq!(
+            definitions["Page_for_Info"]["properties"],
+            json!({
+                "items": { "type": "array", "items": { "$ref": "#/definitions/Info" } },
+                "first": {
+                    "anyOf": [{ "$ref": "#/definitions/Wrapper_for_Info" }, { "type": "null" }],
+                },
+            })
+        );
+        assert_eq!(
+            definitions["Wrapper_for_Info"],
+            json!({ "$ref": "#/definitions/Info" })
+        );
+        assert_eq!(
+            definitions["Page"]["properties"]["first"],
+            json!({ "anyOf": [{ "$ref": "#/definitions/Wrapper_for_Value" }, { "type": "null" }] })
+        );
+        assert_eq!(definitions["Wrapper_for_Value"], json!({}));
+    }
+    #[test]
+    fn it_should_not_leave_dangling_refs_to_near_sdk_types() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse_str(
+                    r#"
+                    #[derive(Serialize)]
+                    struct Budget { gas: near_sdk::Gas, deposit: NearToken }
+
+                    #[near_bindgen]
+                    impl Contract {
+                        pub fn set(&mut self, gas: Gas, deposit: NearToken, key: PublicKey) {}
+                        pub fn budget(&self) -> Budget {}
+                    }"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let abi = abi_root(&contract).unwrap();
+        let definitions = &abi["body"]["root_schema"]["definitions"];
+        assert_eq!(
+            super::dangling_refs(&abi["body"], "#/definitions/", definitions),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            definitions["Budget"]["properties"]["gas"],
+            json!({ "type": "string", "pattern": "^[0-9]+$" })
+        );
+    }
+}
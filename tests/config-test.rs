@@ -0,0 +1,201 @@
+mod parse {
+    use near_syn::{config::Config, ts::SdkVersion};
+    use std::path::PathBuf;
+
+    #[test]
+    fn it_should_read_all_outputs() {
+        let config = Config::parse(
+            r#"
+# Inputs
+crate = "contract/Cargo.toml"
+no-now = true
+naming = { contract = "Market" }
+
+[ts]
+output = 'web/src/bindings'
+split = true
+zod = true
+sdk = "3"
+prelude-from = "@near/prelude"
+
+[ts.types]
+"Timestamp" = "bigint"
+U64 = """
+bigint"""
+
+[md]
+output = "docs/contract.md"
+readme = "README.md"
+sections = [
+    "methods-table",
+    "types", # trailing comma
+]
+start-marker = "<!-- BEGIN {section} -->"
+
+[abi]
+output = "abi.json"
+
+[schema]
+output = "schema.json"
+"#,
+            "near-syn.toml",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.crate_manifest,
+            Some(PathBuf::from("contract/Cargo.toml"))
+        );
+        assert!(config.files.is_empty());
+        assert!(config.no_now);
+        assert_eq!(config.contract_name, Some("Market".to_string()));
+
+        let ts = config.ts.unwrap();
+        assert_eq!(ts.output, PathBuf::from("web/src/bindings"));
+        assert!(ts.split);
+        assert!(ts.options.zod);
+        assert!(!ts.options.factory);
+        assert_eq!(ts.options.sdk, SdkVersion::V3);
+        assert_eq!(ts.options.prelude_module, Some("@near/prelude".to_string()));
+        assert_eq!(
+            ts.options.types,
+            vec![
+                ("Timestamp".to_string(), "bigint".to_string()),
+                ("U64".to_string(), "bigint".to_string()),
+            ]
+        );
+
+        let md = config.md.unwrap();
+        assert_eq!(md.output, Some(PathBuf::from("docs/contract.md")));
+        assert_eq!(md.readme, Some(PathBuf::from("README.md")));
+        assert_eq!(md.sections, vec!["methods-table", "types"]);
+        assert_eq!(
+            md.start_marker,
+            Some("<!-- BEGIN {section} -->".to_string())
+        );
+        assert_eq!(md.end_marker, None);
+
+        assert_eq!(config.abi, Some(PathBuf::from("abi.json")));
+        assert_eq!(config.schema, Some(PathBuf::from("schema.json")));
+    }
+
+    #[test]
+    fn it_should_reject_md_without_output_nor_readme() {
+        let err = Config::parse("[md]\nsections = [\"types\"]\n", "near-syn.toml").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid configuration in `near-syn.toml`: `md` needs either `output` or `readme`"
+        );
+    }
+
+    #[test]
+    fn it_should_reject_values_of_the_wrong_type() {
+        let err = Config::parse("[ts]\noutput = \"a.ts\"\nzod = 1\n", "near-syn.toml").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected a boolean"
+        );
+        assert_eq!(err.location().unwrap().line, 3);
+
+        let err = Config::parse(
+            "[md]\nreadme = \"README.md\"\nend-marker = \"<!-- END -->\"\n",
+            "near-syn.toml",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid configuration in `near-syn.toml`: `md.end-marker` should contain `{section}`"
+        );
+
+        let err = Config::parse("[ts]\nsdk = 6\noutput = \"a.ts\"\n", "near-syn.toml").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported near-sdk version `6`, expected 3, 4 or 5"
+        );
+        assert_eq!(err.location().unwrap().line, 2);
+    }
+
+    #[test]
+    fn it_should_report_toml_syntax_errors_with_location() {
+        let err = Config::parse("no-now = true\n[ts\n", "near-syn.toml").unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.file, Some(PathBuf::from("near-syn.toml")));
+        assert_eq!(location.line, 2);
+    }
+}
+
+mod find {
+    use near_syn::config::Config;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn it_should_prefer_near_syn_toml() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package.metadata.near-syn.abi]\noutput = \"cargo.json\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("near-syn.toml"),
+            "[abi]\noutput = \"abi.json\"\n",
+        )
+        .unwrap();
+
+        let config = Config::find(dir.path()).unwrap().unwrap();
+        assert_eq!(config.abi, Some(dir.path().join("abi.json")));
+        assert_eq!(config.crate_manifest, Some(dir.path().join("Cargo.toml")));
+    }
+
+    #[test]
+    fn it_should_read_cargo_metadata() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "contract"
+version = "0.1.0"
+
+[[bin]]
+name = "a"
+
+[package.metadata.near-syn]
+files = ["src/lib.rs", "src/types.rs"]
+
+[package.metadata.near-syn.md]
+readme = "README.md"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::find(dir.path()).unwrap().unwrap();
+        assert_eq!(config.crate_manifest, None);
+        assert_eq!(
+            config.files,
+            vec![
+                dir.path().join("src/lib.rs"),
+                dir.path().join("src/types.rs")
+            ]
+        );
+        assert_eq!(
+            config.md.unwrap().readme,
+            Some(dir.path().join("README.md"))
+        );
+    }
+
+    #[test]
+    fn it_should_return_none_without_configuration() {
+        let dir = tempdir().unwrap();
+        assert!(Config::find(dir.path()).unwrap().is_none());
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"a\"\n").unwrap();
+        assert!(Config::find(dir.path()).unwrap().is_none());
+
+        let err = Config::from_file(dir.path().join("Cargo.toml")).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("missing `[package.metadata.near-syn]` table"));
+    }
+}
@@ -0,0 +1,225 @@
+mod from_crate_root {
+    use near_syn::{
+        contract::{Contract, NearItem},
+        Error,
+    };
+    use std::{
+        io::Write,
+        path::{Path, PathBuf},
+    };
+    use tempfile::TempDir;
+
+    fn crate_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/input/crate/src/lib.rs")
+    }
+
+    fn item_name(item: &NearItem) -> String {
+        match item {
+            NearItem::Impl(_) => "impl".to_string(),
+            NearItem::Struct(item_struct) => item_struct.ident.to_string(),
+            NearItem::Enum(item_enum) => item_enum.ident.to_string(),
+            NearItem::Type(item_type) => item_type.ident.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_should_follow_mod_declarations() {
+        let contract = Contract::from_crate_root(crate_root()).unwrap();
+        let src = crate_root().parent().unwrap().to_path_buf();
+
+        let items = contract
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item_name(item),
+                    item.module.join("::"),
+                    item.file
+                        .as_ref()
+                        .unwrap()
+                        .strip_prefix(&src)
+                        .unwrap()
+                        .to_path_buf(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                (
+                    "Nested".to_string(),
+                    "a::nested".to_string(),
+                    PathBuf::from("a/nested.rs")
+                ),
+                ("A".to_string(), "a".to_string(), PathBuf::from("a.rs")),
+                (
+                    "B".to_string(),
+                    "b::inner".to_string(),
+                    PathBuf::from("other/b_file.rs")
+                ),
+                (
+                    "impl".to_string(),
+                    "c".to_string(),
+                    PathBuf::from("c/mod.rs")
+                ),
+                ("impl".to_string(), "".to_string(), PathBuf::from("lib.rs")),
+            ]
+        );
+        assert_eq!(contract.name, Some("Contract".to_string()));
+        assert_eq!(contract.view_methods, vec!["get_total"]);
+        assert_eq!(contract.change_methods, vec!["set_total"]);
+    }
+
+    #[test]
+    fn it_should_record_the_files_read() {
+        let contract = Contract::from_crate_root(crate_root()).unwrap();
+        let src = crate_root().parent().unwrap().to_path_buf();
+
+        let files = contract
+            .files
+            .iter()
+            .map(|file| file.strip_prefix(&src).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("lib.rs"),
+                PathBuf::from("a.rs"),
+                PathBuf::from("a/nested.rs"),
+                PathBuf::from("other/b_file.rs"),
+                PathBuf::from("c/mod.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_not_follow_mod_declarations_when_pushing_single_files() {
+        let mut contract = Contract::new();
+        contract.push_file(crate_root()).unwrap();
+        assert_eq!(contract.items.len(), 1);
+        assert!(contract.items[0].module.is_empty());
+    }
+
+    #[test]
+    fn it_should_fail_when_module_file_is_missing() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("lib.rs");
+        let mut file = std::fs::File::create(&root).unwrap();
+        writeln!(file, "mod a;\nmod missing;").unwrap();
+        std::fs::File::create(dir.path().join("a.rs")).unwrap();
+
+        let err = Contract::from_crate_root(&root).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "file not found for module `missing`, tried `{}` and `{}`",
+                dir.path().join("missing.rs").display(),
+                dir.path().join("missing/mod.rs").display(),
+            )
+        );
+        assert!(matches!(err, Error::ModuleNotFound { .. }));
+        let location = err.location().unwrap();
+        assert_eq!(location.file.as_deref(), Some(root.as_path()));
+        assert_eq!((location.line, location.column), (2, 5));
+    }
+}
+
+mod push_ast {
+    use near_syn::{contract::Contract, ts::ts_items};
+
+    fn ts(src: &str) -> String {
+        let mut contract = Contract::new();
+        contract.push_ast(syn::parse_str(src).unwrap()).unwrap();
+        let mut buf = Vec::new();
+        ts_items(&mut buf, &contract).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn it_should_resolve_paths_through_use_declarations_and_submodules() {
+        let ts = ts(r#"
+            use crate::{tokens, other::{self, Other as Renamed}};
+
+            mod inner {}
+
+            type A = tokens::Token;
+            type B = other::Other;
+            type C = inner::Inner;
+            type D = Renamed::Nested;
+            "#);
+        assert!(ts.contains("export type A = Token;"));
+        assert!(ts.contains("export type B = Other;"));
+        assert!(ts.contains("export type C = Inner;"));
+        assert!(ts.contains("export type D = Nested;"));
+    }
+
+    #[test]
+    fn it_should_resolve_renamed_imports() {
+        let ts = ts(r#"
+            use near_sdk::json_types::U128 as Amount;
+            use std::collections::{HashMap as Map, self as col};
+            use serde_json::Value;
+
+            type A = Map<AccountId, Amount>;
+            type B = col::BTreeMap<String, Value>;
+            "#);
+        assert!(ts.contains("export type A = Record<AccountId, U128>;"));
+        assert!(ts.contains("export type B = Record<string, unknown>;"));
+    }
+
+    #[test]
+    fn it_should_not_resolve_type_parameters_shadowing_imports() {
+        let ts = ts(r#"
+            use near_sdk::json_types::U128 as T;
+
+            #[derive(Serialize)]
+            struct Page<T> { items: Vec<T> }
+            type Amounts = Vec<T>;
+            "#);
+        assert!(ts.contains("export type Page<T> = {"));
+        assert!(ts.contains("    items: T[];"));
+        assert!(ts.contains("export type Amounts = U128[];"));
+    }
+
+    #[test]
+    fn it_should_record_imports_per_module() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                syn::parse_str(
+                    r#"
+                    use near_sdk::json_types::U128 as Amount;
+                    mod inner {
+                        use std::collections::HashMap as Map;
+                    }
+                    "#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let top = &contract.imports[&Vec::new()];
+        assert_eq!(top["Amount"], "near_sdk::json_types::U128");
+        assert!(!top.contains_key("Map"));
+        let inner = &contract.imports[&vec!["inner".to_string()]];
+        assert_eq!(inner["Map"], "std::collections::HashMap");
+    }
+
+    #[test]
+    fn it_should_not_resolve_paths_through_modules_not_in_scope() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(syn::parse_str("type A = tokens::Token;").unwrap())
+            .unwrap();
+        let unresolved = contract.unresolved_paths();
+        assert_eq!(unresolved.len(), 1);
+        assert!(matches!(
+            unresolved[0],
+            near_syn::Error::UnresolvedPath { .. }
+        ));
+
+        let mut buf = Vec::new();
+        ts_items(&mut buf, &contract).unwrap();
+        assert!(String::from_utf8_lossy(&buf).contains("export type A = Token;"));
+    }
+}
@@ -49,6 +49,105 @@ export type AccountId = string;
  * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/type.ValidAccountId.html.
  */
 export type ValidAccountId = string;
+
+/**
+ * Represents the amount of gas, encoded as a `string`.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/struct.Gas.html.
+ */
+export type Gas = string;
+
+/**
+ * Public key encoded as a `string`, prefixed by its curve, _e.g._, `ed25519:`.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/struct.PublicKey.html.
+ */
+export type PublicKey = string;
+
+/**
+ * Timestamp in nanoseconds.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.Timestamp.html.
+ */
+export type Timestamp = RawU64;
+
+/**
+ * Duration in nanoseconds.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.Duration.html.
+ */
+export type Duration = RawU64;
+
+/**
+ * Storage usage in bytes.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.StorageUsage.html.
+ */
+export type StorageUsage = RawU64;
+
+/**
+ * Hash of 32 bytes.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/type.CryptoHash.html.
+ */
+export type CryptoHash = number[];
+
+/**
+ * Hash of 32 bytes encoded in base58 into a `string`.
+ * See https://docs.rs/near-sdk/4.0.0-pre.4/near_sdk/json_types/type.Base58CryptoHash.html.
+ */
+export type Base58CryptoHash = string;
+
+/**
+ * Raw `u64` integer serialized as a JSON `number`.
+ * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U64`.
+ */
+export type RawU64 = number;
+
+/**
+ * Raw `i64` integer serialized as a JSON `number`.
+ * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I64`.
+ */
+export type RawI64 = number;
+
+/**
+ * Raw `u128` integer serialized as a JSON `number`.
+ * Values above `Number.MAX_SAFE_INTEGER` lose precision, prefer `U128`.
+ */
+export type RawU128 = number;
+
+/**
+ * Raw `i128` integer serialized as a JSON `number`.
+ * Values beyond `Number.MAX_SAFE_INTEGER` lose precision, prefer `I128`.
+ */
+export type RawI128 = number;
+
+/**
+ * Options of a call to a change method.
+ * A deposit can only be attached to payable methods, see `PayableCallOptions`.
+ */
+export type CallOptions = {{
+    /**
+     * Amount of gas to attach to the call, in gas units.
+     */
+    gas?: string | bigint;
+
+    /**
+     * Metadata passed to the wallet when the call is signed through it.
+     */
+    walletMeta?: string;
+
+    /**
+     * URL the wallet redirects to after the call is signed through it.
+     */
+    walletCallbackUrl?: string;
+
+    attachedDeposit?: never;
+}};
+
+/**
+ * Options of a call to a payable change method.
+ */
+export type PayableCallOptions = Omit<CallOptions, "attachedDeposit"> & {{
+    /**
+     * Amount of NEAR to attach to the call, in yoctoNEAR.
+     */
+    attachedDeposit?: string;
+}};
 {}
 export const {}Methods = {{
     viewMethods: [{}
@@ -0,0 +1,7 @@
+mod nested;
+
+/// Declared in `a.rs`.
+#[derive(Serialize)]
+pub struct A {
+    pub value: u32,
+}
@@ -0,0 +1,6 @@
+/// Declared in `a/nested.rs`.
+#[derive(Serialize)]
+pub enum Nested {
+    V1,
+    V2,
+}
@@ -0,0 +1,9 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the total.
+    pub fn set_total(&mut self, total: U128) {
+        self.total = total;
+    }
+}
@@ -0,0 +1,20 @@
+mod a;
+#[path = "other/b_file.rs"]
+mod b;
+mod c;
+
+#[cfg(test)]
+mod tests;
+
+#[near_bindgen]
+struct Contract {
+    total: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the total.
+    pub fn get_total(&self) -> U128 {
+        self.total
+    }
+}
@@ -0,0 +1,7 @@
+pub mod inner {
+    /// Declared in an inline module of `other/b_file.rs`.
+    #[derive(Serialize)]
+    pub struct B {
+        pub flag: bool,
+    }
+}
@@ -0,0 +1,54 @@
+
+/**
+ * Declared in `a/nested.rs`.
+ */
+export enum Nested {
+    /**
+     */
+    V1 = "V1",
+
+    /**
+     */
+    V2 = "V2",
+
+}
+
+/**
+ * Declared in `a.rs`.
+ */
+export type A = {
+    /**
+     */
+    value: number;
+
+}
+
+/**
+ * Declared in an inline module of `other/b_file.rs`.
+ */
+export type B = {
+    /**
+     */
+    flag: boolean;
+
+}
+
+/**
+ */
+export interface Contract {
+    /**
+     * Sets the total.
+     */
+    set_total(args: { total: U128 }, options?: CallOptions): Promise<void>;
+
+}
+
+/**
+ */
+export interface Contract {
+    /**
+     * Returns the total.
+     */
+    get_total(): Promise<U128>;
+
+}
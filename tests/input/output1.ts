@@ -51,11 +51,11 @@ export type B = {
 export enum E {
     /**
      */
-    V1,
+    V1 = "V1",
 
     /**
      */
-    V2,
+    V2 = "V2",
 
 }
 
@@ -76,7 +76,7 @@ export interface C {
     /**
      * Set f128.
      */
-    set_f128(args: { value: U128 }, gas?: any): Promise<void>;
+    set_f128(args: { value: U128 }, options?: CallOptions): Promise<void>;
 
     /**
      */
@@ -84,12 +84,12 @@ export interface C {
 
     /**
      */
-    more_types(args: { key: U128, tuple: [string, number[]] }, gas?: any): Promise<void>;
+    more_types(args: { key: U128, tuple: [string, number[]] }, options?: CallOptions): Promise<void>;
 
     /**
      * Pay to set f128.
      */
-    set_f128_with_sum(args: { a_value: U128, other_value: U128 }, gas?: any, amount?: any): Promise<void>;
+    set_f128_with_sum(args: { a_value: U128, other_value: U128 }, options?: PayableCallOptions): Promise<void>;
 
 }
 
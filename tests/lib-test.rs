@@ -37,6 +37,25 @@ mod near_impl {
         assert!(item_impl.is_bindgen());
     }
 
+    #[test]
+    fn it_should_return_true_when_near_is_present() {
+        let item_impl = parse_item_impl(quote! {
+            #[near]
+            impl Contract {
+                pub fn get(&self, f128: U128) -> U128 { f128 }
+            }
+        });
+        assert!(item_impl.is_bindgen());
+
+        let item_impl = parse_item_impl(quote! {
+            #[near_sdk::near]
+            impl IContract for Contract {
+                pub fn get(&self, f128: U128) -> U128 { f128 }
+            }
+        });
+        assert!(item_impl.is_bindgen());
+    }
+
     #[test]
     fn it_should_return_trait_name_if_present() {
         let item_impl = parse_item_impl(quote! {
@@ -73,3 +92,69 @@ mod near_impl {
         assert_eq!(item_impl.get_impl_name(), None);
     }
 }
+
+mod near_method {
+
+    use near_syn::NearMethod;
+    use syn::{parse_str, ImplItemMethod};
+
+    #[test]
+    fn it_should_detect_handle_result_methods() {
+        let method: ImplItemMethod =
+            parse_str("pub fn f(&self) -> Result<u32, String> { Ok(1) }").unwrap();
+        assert!(!method.is_handle_result());
+
+        let method: ImplItemMethod =
+            parse_str("#[handle_result] pub fn f(&self) -> Result<u32, String> { Ok(1) }").unwrap();
+        assert!(method.is_handle_result());
+    }
+}
+
+mod near_struct {
+
+    use near_syn::{NearBindgen, NearSerde};
+    use syn::{parse_str, ItemEnum, ItemStruct};
+
+    #[test]
+    fn it_should_detect_near_contract_state() {
+        let item_struct: ItemStruct = parse_str("#[near_bindgen] struct Contract {}").unwrap();
+        assert!(item_struct.is_bindgen());
+
+        let item_struct: ItemStruct =
+            parse_str("#[near(contract_state)] struct Contract {}").unwrap();
+        assert!(item_struct.is_bindgen());
+
+        let item_struct: ItemStruct =
+            parse_str("#[near(contract_state, serializers = [borsh, json])] struct Contract {}")
+                .unwrap();
+        assert!(item_struct.is_bindgen());
+        assert!(item_struct.is_serde());
+
+        let item_struct: ItemStruct = parse_str("#[near] struct Contract {}").unwrap();
+        assert!(!item_struct.is_bindgen());
+        assert!(!item_struct.is_serde());
+    }
+
+    #[test]
+    fn it_should_detect_near_json_serializer() {
+        let item_enum: ItemEnum =
+            parse_str("#[near(serializers = [json])] enum E { A, B }").unwrap();
+        assert!(item_enum.is_serialize());
+        assert!(item_enum.is_deserialize());
+
+        let item_enum: ItemEnum = parse_str(
+            "#[near(serializers = [borsh(use_discriminant = true), json])] enum E { A = 1 }",
+        )
+        .unwrap();
+        assert!(item_enum.is_serde());
+
+        let item_enum: ItemEnum =
+            parse_str("#[near(serializers = [borsh])] enum E { A, B }").unwrap();
+        assert!(!item_enum.is_serde());
+
+        let item_struct: ItemStruct =
+            parse_str("#[derive(Serialize)] #[near(serializers = [borsh])] struct S {}").unwrap();
+        assert!(item_struct.is_serialize());
+        assert!(!item_struct.is_deserialize());
+    }
+}
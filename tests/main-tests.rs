@@ -68,6 +68,52 @@ mod ts {
         near_ts().assert().code(0).stdout(output("", "", "", ""));
     }
 
+    #[test]
+    fn transpile_zero_rust_files_to_ts_with_sdk_5() {
+        let output = near_ts().arg("--sdk").arg("5").output().unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("https://docs.rs/near-sdk/5.0.0/near_sdk/"));
+        assert!(stdout.contains("export type NearToken = string;"));
+        assert!(!stdout.contains("ValidAccountId"));
+    }
+
+    #[test]
+    fn reject_unsupported_sdk_version() {
+        let output = near_ts().arg("--sdk").arg("2").output().unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("unsupported near-sdk version `2`, expected 3, 4 or 5"));
+    }
+
+    #[test]
+    fn transpile_zero_rust_files_to_ts_with_prelude_from_module() {
+        let output = near_ts()
+            .arg("--prelude-from")
+            .arg("./near-sdk")
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("\nimport type { U64, I64, U128, "));
+        assert!(stdout.contains(" } from \"./near-sdk\";\n\n"));
+        assert!(!stdout.contains("export type U128"));
+    }
+
+    #[test]
+    fn emit_prelude_only() {
+        let paths = rust_test_files();
+        let expected = output("", "", "", "");
+        let prelude = &expected[..expected.find("export const Methods").unwrap()];
+
+        near_ts()
+            .arg("--prelude-only")
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout(prelude.to_string());
+    }
+
     #[test]
     fn transpile_single_rust_file_to_ts() {
         let paths = rust_test_files();
@@ -99,12 +145,289 @@ mod ts {
 
         paths.into_iter().for_each(|path| path.close().unwrap());
     }
+
+    #[test]
+    fn transpile_rust_crate_to_ts() {
+        near_ts()
+            .arg("--crate")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/input/crate/Cargo.toml"
+            ))
+            .assert()
+            .code(0)
+            .stdout(output(
+                include_str!("input/output-crate.ts"),
+                "Contract",
+                "get_total",
+                "set_total",
+            ));
+    }
+
+    #[test]
+    fn transpile_rust_crate_to_split_ts() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/input/crate/Cargo.toml");
+        near_ts()
+            .arg("--split")
+            .arg("-o")
+            .arg(dir.path())
+            .arg("--crate")
+            .arg(manifest)
+            .assert()
+            .code(0)
+            .stdout("");
+
+        let read = |name| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        let expected = output(
+            include_str!("input/output-crate.ts"),
+            "Contract",
+            "get_total",
+            "set_total",
+        );
+        let contract_start = expected
+            .find("/**\n */\nexport interface Contract")
+            .unwrap();
+        assert_eq!(read("types.ts"), &expected[..contract_start]);
+
+        let contract = read("contract.ts");
+        assert!(contract.contains(
+            "\nimport type { A, B } from \"./types\";\nimport { Nested } from \"./types\";\n\n/**\n */\nexport interface Contract {\n"
+        ));
+        assert!(contract.contains("\nexport const ContractMethods = {\n"));
+        assert!(!contract.contains("export type A = "));
+
+        let index = read("index.ts");
+        assert!(index.ends_with(
+            ", DO NOT MODIFY!\n\nexport * from \"./types\";\nexport * from \"./contract\";\n"
+        ));
+
+        near_ts()
+            .arg("--split")
+            .arg("--check")
+            .arg("-o")
+            .arg(dir.path())
+            .arg("--crate")
+            .arg(manifest)
+            .assert()
+            .code(0)
+            .stdout("");
+    }
+
+    #[test]
+    fn override_types_and_contract_name() {
+        let stdout = near_ts()
+            .args(["--contract-name", "Market", "--type", "A=bigint", "--type"])
+            .arg("U128=bigint")
+            .arg("--crate")
+            .arg("tests/input/crate/Cargo.toml")
+            .output()
+            .unwrap()
+            .stdout;
+        let stdout = String::from_utf8(stdout).unwrap();
+        assert!(stdout.contains("export type U128 = bigint;\n"));
+        assert!(stdout.contains("export type A = bigint;\n"));
+        assert_eq!(stdout.matches("export interface A ").count(), 0);
+        assert_eq!(stdout.matches("export interface Market {").count(), 2);
+        assert!(stdout.contains("export const MarketMethods = {"));
+        assert!(!stdout.contains("interface Contract "));
+    }
+
+    #[test]
+    fn reject_invalid_type_override() {
+        let output = near_ts().args(["--type", "A"]).output().unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("expected `RUST=TS`, found `A`"));
+    }
+
+    #[test]
+    fn reject_split_without_output() {
+        near_ts()
+            .arg("--split")
+            .assert()
+            .code(1)
+            .stderr("error: --split needs the output directory given by --output\n");
+    }
+
+    #[test]
+    fn transpile_rust_crate_to_ts_with_actions() {
+        let expected = output(
+            include_str!("input/output-crate.ts"),
+            "Contract",
+            "get_total",
+            "set_total",
+        );
+
+        near_ts()
+            .arg("--actions")
+            .arg("--crate")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/input/crate/Cargo.toml"
+            ))
+            .assert()
+            .code(0)
+            .stdout(format!(
+                r#"{}
+/**
+ * A `FunctionCall` action, as expected by `@near-wallet-selector`.
+ */
+export type FunctionCallAction<A> = {{
+    type: "FunctionCall";
+    params: {{
+        methodName: string;
+        args: A;
+        gas: string;
+        deposit: string;
+    }};
+}};
+
+const DEFAULT_GAS = "30000000000000";
+
+export const ContractActions = {{
+    set_total(args: {{ total: U128 }}, options: CallOptions = {{}}): FunctionCallAction<{{ total: U128 }}> {{
+        return {{
+            type: "FunctionCall",
+            params: {{
+                methodName: "set_total",
+                args,
+                gas: (options.gas ?? DEFAULT_GAS).toString(),
+                deposit: "0",
+            }},
+        }};
+    }},
+}};
+"#,
+                expected
+            ));
+    }
+
+    #[test]
+    fn transpile_rust_crate_to_ts_with_factory() {
+        let expected = output(
+            include_str!("input/output-crate.ts"),
+            "Contract",
+            "get_total",
+            "set_total",
+        )
+        .replacen(
+            "DO NOT MODIFY!\n\n",
+            r#"DO NOT MODIFY!
+
+import BN from "bn.js";
+import { Account, Contract as NearContract } from "near-api-js";
+
+"#,
+            1,
+        );
+
+        near_ts()
+            .arg("--factory")
+            .arg("--crate")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/input/crate/Cargo.toml"
+            ))
+            .assert()
+            .code(0)
+            .stdout(format!(
+                r#"{}
+function nearCallOptions(options: PayableCallOptions = {{}}) {{
+    return {{
+        gas: options.gas === undefined ? undefined : new BN(options.gas.toString()),
+        amount: options.attachedDeposit === undefined ? undefined : new BN(options.attachedDeposit),
+        meta: options.walletMeta,
+        callbackUrl: options.walletCallbackUrl,
+    }};
+}}
+
+export function connectContract(account: Account, contractId: string): Contract {{
+    const contract: any = new NearContract(account, contractId, ContractMethods);
+    return {{
+        get_total: () => contract.get_total({{}}),
+        set_total: (args, options) => contract.set_total({{ args, ...nearCallOptions(options) }}),
+    }} as Contract;
+}}
+"#,
+                expected
+            ));
+    }
+
+    #[test]
+    fn transpile_rust_crate_to_ts_with_zod() {
+        let expected = output(
+            include_str!("input/output-crate.ts"),
+            "Contract",
+            "get_total",
+            "set_total",
+        )
+        .replacen(
+            "DO NOT MODIFY!\n\n",
+            "DO NOT MODIFY!\n\nimport { z } from \"zod\";\n\n",
+            1,
+        );
+
+        near_ts()
+            .arg("--zod")
+            .arg("--crate")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/input/crate/Cargo.toml"
+            ))
+            .assert()
+            .code(0)
+            .stdout(format!(
+                r#"{}
+// Schemas to validate values received from the NEAR RPC.
+
+export const U64Schema = z.string().regex(/^[0-9]+$/);
+export const I64Schema = z.string().regex(/^-?[0-9]+$/);
+export const U128Schema = z.string().regex(/^[0-9]+$/);
+export const I128Schema = z.string().regex(/^-?[0-9]+$/);
+export const Base64VecU8Schema = z.string();
+export const BalanceSchema = U128Schema;
+export const AccountIdSchema = z.string();
+export const ValidAccountIdSchema = z.string();
+export const GasSchema = z.string().regex(/^[0-9]+$/);
+export const PublicKeySchema = z.string();
+export const TimestampSchema = z.number().int();
+export const DurationSchema = z.number().int();
+export const StorageUsageSchema = z.number().int();
+export const CryptoHashSchema = z.array(z.number().int()).length(32);
+export const Base58CryptoHashSchema = z.string();
+export const RawU64Schema = z.number().int();
+export const RawI64Schema = z.number().int();
+export const RawU128Schema = z.number().int();
+export const RawI128Schema = z.number().int();
+
+export const NestedSchema: z.ZodType<Nested> = z.nativeEnum(Nested);
+
+export const ASchema: z.ZodType<A> = z.object({{
+    value: z.number().int(),
+}});
+
+export const BSchema: z.ZodType<B> = z.object({{
+    flag: z.boolean(),
+}});
+
+export const ContractResultSchemas = {{
+    get_total: U128Schema,
+}};
+
+export function parseContractResult<M extends keyof typeof ContractResultSchemas>(method: M, result: unknown): z.infer<typeof ContractResultSchemas[M]> {{
+    return ContractResultSchemas[method].parse(result) as z.infer<typeof ContractResultSchemas[M]>;
+}}
+"#,
+                expected
+            ));
+    }
 }
 
 mod md {
 
     use super::{near_cmd, rust_test_files};
     use assert_cmd::Command;
+    use std::io::Write;
 
     fn output(init_methods: &str, view_methods: &str, change_methods: &str, text: &str) -> String {
         format!(
@@ -151,4 +474,712 @@ mod md {
 
         paths.into_iter().for_each(|path| path.close().unwrap());
     }
+
+    fn marker(section: &str, kind: &str) -> String {
+        format!(
+            "<!-- AUTOGENERATED near-syn:{}:{}, DO NOT MODIFY -->",
+            section, kind
+        )
+    }
+
+    #[test]
+    fn embed_sections_in_readme() {
+        let paths = rust_test_files();
+        let mut readme = tempfile::NamedTempFile::new().unwrap();
+        for section in ["init", "methods:I", "types"] {
+            writeln!(readme, "## {}\n", section).unwrap();
+            writeln!(
+                readme,
+                "{}\n{}\n",
+                marker(section, "start"),
+                marker(section, "end")
+            )
+            .unwrap();
+        }
+        let readme = readme.into_temp_path();
+
+        near_md()
+            .arg("--readme")
+            .arg(readme.to_str().unwrap())
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout("")
+            .stderr("");
+
+        let content = std::fs::read_to_string(&readme).unwrap();
+        assert!(content.starts_with(
+            &[
+                "## init",
+                "",
+                &marker("init", "start"),
+                "",
+                "### :rocket: `init_here` (_constructor_)",
+                "",
+                "```typescript",
+                "init_here: { f128: U128 };",
+                "```",
+                "",
+                "init func",
+                &marker("init", "end"),
+                "",
+                "## methods:I",
+                "",
+                &marker("methods:I", "start"),
+                "",
+                "## Methods for `I` interface",
+                "",
+                "### :eyeglasses: `get`",
+                "",
+                "```typescript",
+                "get(): Promise<U128>;",
+                "```",
+                "",
+                "Single-line comment for get",
+                &marker("methods:I", "end"),
+                "",
+                "## types",
+                "",
+                &marker("types", "start"),
+                "",
+                "### `AType`",
+                "",
+                "```typescript",
+                "export type AType = number;",
+                "```",
+                "",
+                "",
+                "### `BType`",
+                "",
+                "```typescript",
+                "export type BType = number;",
+                "```",
+                "",
+                "Doc-comments for a type def",
+                "",
+            ]
+            .join("\n")
+        ));
+        assert!(content.ends_with(&format!(
+            "\n```typescript\nexport type A_in_mod = number;\n```\n\n{}\n\n",
+            marker("types", "end")
+        )));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+
+    #[test]
+    fn warn_on_unmatched_readme_markers() {
+        let paths = rust_test_files();
+        let content = [
+            "# Contract",
+            &marker("unknown", "start"),
+            "kept",
+            &marker("unknown", "end"),
+            &marker("methods:Unimplemented", "start"),
+            "kept as well",
+            &marker("methods:Unimplemented", "end"),
+            &marker("full-docs", "start"),
+            "kept too",
+            "",
+        ]
+        .join("\n");
+        let mut readme = tempfile::NamedTempFile::new().unwrap();
+        write!(readme, "{}", content).unwrap();
+        let readme = readme.into_temp_path();
+
+        near_md()
+        .arg("--readme")
+        .arg(readme.to_str().unwrap())
+        .arg(paths[0].to_str().unwrap())
+        .assert()
+        .code(0)
+        .stderr(format!(
+            "warning: {0}:2: unknown section `{1}`, leaving it unchanged\n\
+             warning: {0}:5: trait `Unimplemented` of section `{2}` is not implemented by the contract, leaving it unchanged\n\
+             warning: {0}:8: start marker `{3}` has no matching end marker, leaving the rest of the file unchanged\n",
+            readme.display(),
+            marker("unknown", "start"),
+            marker("methods:Unimplemented", "start"),
+            marker("full-docs", "start"),
+        ));
+        assert_eq!(std::fs::read_to_string(&readme).unwrap(), content);
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+
+    #[test]
+    fn regenerate_readme_sections_with_custom_markers() {
+        let paths = rust_test_files();
+        let content = [
+            "<!-- BEGIN init -->",
+            "outdated init",
+            "<!-- END init -->",
+            &marker("types", "start"),
+            "kept",
+            &marker("types", "end"),
+            "<!-- BEGIN unknown -->",
+            "kept as well",
+            "<!-- END unknown -->",
+            "",
+        ]
+        .join("\n");
+        let mut readme = tempfile::NamedTempFile::new().unwrap();
+        write!(readme, "{}", content).unwrap();
+        let readme = readme.into_temp_path();
+
+        near_md()
+            .args(["--start-marker", "<!-- BEGIN {section} -->"])
+            .args(["--end-marker", "<!-- END {section} -->"])
+            .arg("--readme")
+            .arg(readme.to_str().unwrap())
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stderr(format!(
+                "warning: {}:7: unknown section `<!-- BEGIN unknown -->`, leaving it unchanged\n",
+                readme.display()
+            ));
+        let result = std::fs::read_to_string(&readme).unwrap();
+        assert!(!result.contains("outdated init"));
+        assert!(result.contains("<!-- BEGIN init -->\n\n### :rocket: `init_here`"));
+        assert!(result.contains("\nkept\n"));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+
+    #[test]
+    fn regenerate_only_given_readme_sections() {
+        let paths = rust_test_files();
+        let content = [
+            &marker("init", "start"),
+            "outdated init",
+            &marker("init", "end"),
+            &marker("unknown", "start"),
+            "kept",
+            &marker("unknown", "end"),
+            "",
+        ]
+        .join("\n");
+        let mut readme = tempfile::NamedTempFile::new().unwrap();
+        write!(readme, "{}", content).unwrap();
+        let readme = readme.into_temp_path();
+
+        near_md()
+            .args(["--section", "init", "--readme"])
+            .arg(readme.to_str().unwrap())
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stderr("");
+        let result = std::fs::read_to_string(&readme).unwrap();
+        assert!(!result.contains("outdated init"));
+        assert!(result.contains("`init_here`"));
+        assert!(result.contains("\nkept\n"));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+}
+
+mod abi {
+
+    use super::{near_cmd, rust_test_files};
+    use assert_cmd::Command;
+
+    fn near_abi() -> Command {
+        near_cmd("abi")
+    }
+
+    #[test]
+    fn transpile_single_rust_file_to_abi() {
+        let paths = rust_test_files();
+
+        near_abi()
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout(include_str!("input/output1.abi.json"));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+
+    #[test]
+    fn transpile_multiple_rust_files_to_abi() {
+        let paths = rust_test_files();
+
+        near_abi()
+            .args(&paths[1..])
+            .assert()
+            .code(0)
+            .stdout(include_str!("input/output2.abi.json"));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+}
+
+mod schema {
+
+    use super::{near_cmd, rust_test_files};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn transpile_single_rust_file_to_schema() {
+        let paths = rust_test_files();
+
+        near_cmd("schema")
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout(include_str!("input/output1.schema.json"));
+
+        paths.into_iter().for_each(|path| path.close().unwrap());
+    }
+
+    #[test]
+    fn accept_any_value_for_type_parameters() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "#[derive(Serialize)]\nstruct Page<T> {{ items: Vec<T>, next: Option<U64> }}"
+        )
+        .unwrap();
+        let path = file.into_temp_path();
+
+        let stdout = near_cmd("schema")
+            .arg(path.to_str().unwrap())
+            .output()
+            .unwrap()
+            .stdout;
+        let schema: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+        assert_eq!(
+            schema["$defs"]["Page"]["properties"]["items"],
+            serde_json::json!({ "type": "array", "items": {} })
+        );
+        assert!(!String::from_utf8_lossy(&stdout).contains("#/$defs/T"));
+    }
+}
+
+mod check {
+
+    use super::{near_cmd, rust_test_files};
+    use assert_cmd::Command;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_output_to_file() {
+        let paths = rust_test_files();
+        let dir = tempdir().unwrap();
+        let output = dir.path().join("bindings.ts");
+
+        let stdout = near_cmd("ts")
+            .arg(paths[0].to_str().unwrap())
+            .output()
+            .unwrap()
+            .stdout;
+        near_cmd("ts")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout("");
+        assert_eq!(fs::read(&output).unwrap(), stdout);
+    }
+
+    #[test]
+    fn accept_up_to_date_output_ignoring_date_time() {
+        let paths = rust_test_files();
+        let dir = tempdir().unwrap();
+        let output = dir.path().join("bindings.ts");
+
+        Command::cargo_bin("near-syn")
+            .unwrap()
+            .args(["ts", "--output"])
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0);
+        let content = fs::read_to_string(&output).unwrap();
+        assert!(content.contains(" UTC, DO NOT MODIFY!"));
+
+        near_cmd("ts")
+            .arg("--check")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout("");
+        assert_eq!(fs::read_to_string(&output).unwrap(), content);
+    }
+
+    #[test]
+    fn reject_date_time_outside_of_the_header() {
+        let paths = rust_test_files();
+        let dir = tempdir().unwrap();
+        let output = dir.path().join("bindings.ts");
+
+        near_cmd("ts")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0);
+        let content = fs::read_to_string(&output).unwrap();
+        fs::write(
+            &output,
+            content.replacen("\n", "\n// Released on 2022-05-01 10:00:00 UTC\n", 1),
+        )
+        .unwrap();
+
+        near_cmd("ts")
+            .arg("--check")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(1);
+    }
+
+    #[test]
+    fn reject_out_of_date_output_with_diff() {
+        let paths = rust_test_files();
+        let dir = tempdir().unwrap();
+        let output = dir.path().join("bindings.ts");
+
+        near_cmd("ts")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0);
+        let content = fs::read_to_string(&output)
+            .unwrap()
+            .replace("get_f128_other_way", "get_f128_old_way");
+        fs::write(&output, &content).unwrap();
+
+        let result = near_cmd("ts")
+            .arg("--check")
+            .arg("-o")
+            .arg(&output)
+            .arg(paths[0].to_str().unwrap())
+            .output()
+            .unwrap();
+        assert_eq!(result.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(result.stderr).unwrap(),
+            format!("error: `{}` is out of date\n", output.display())
+        );
+        let diff = String::from_utf8(result.stdout).unwrap();
+        assert!(diff.starts_with(&format!(
+            "--- {}\n+++ {}\n@@ ",
+            output.display(),
+            output.display()
+        )));
+        assert!(diff.contains("\n-    get_f128_old_way(args: { key: U128 }): Promise<U128>;\n+    get_f128_other_way(args: { key: U128 }): Promise<U128>;\n"));
+        assert!(
+            diff.contains("\n-        \"get_f128_old_way\",\n+        \"get_f128_other_way\",\n")
+        );
+        assert_eq!(fs::read_to_string(&output).unwrap(), content);
+    }
+
+    #[test]
+    fn check_readme_without_writing_it() {
+        let paths = rust_test_files();
+        let dir = tempdir().unwrap();
+        let readme = dir.path().join("README.md");
+        let content = "# Contract\n\n<!-- AUTOGENERATED near-syn:methods-table:start, DO NOT MODIFY -->\n<!-- AUTOGENERATED near-syn:methods-table:end, DO NOT MODIFY -->\n";
+        fs::write(&readme, content).unwrap();
+
+        let result = near_cmd("md")
+            .arg("--readme")
+            .arg(&readme)
+            .arg("--check")
+            .arg(paths[0].to_str().unwrap())
+            .output()
+            .unwrap();
+        assert_eq!(result.status.code(), Some(1));
+        assert!(String::from_utf8(result.stdout)
+            .unwrap()
+            .contains("\n+| :eyeglasses: `get` |  Single-line comment for get | `U128` |\n"));
+        assert_eq!(fs::read_to_string(&readme).unwrap(), content);
+
+        near_cmd("md")
+            .arg("--readme")
+            .arg(&readme)
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0);
+        near_cmd("md")
+            .arg("--readme")
+            .arg(&readme)
+            .arg("--check")
+            .arg(paths[0].to_str().unwrap())
+            .assert()
+            .code(0)
+            .stdout("");
+    }
+
+    #[test]
+    fn reject_check_without_output() {
+        near_cmd("abi")
+            .arg("--check")
+            .assert()
+            .code(1)
+            .stdout("")
+            .stderr(
+                "error: --check needs the file to check, given either by --output or --readme\n",
+            );
+    }
+}
+
+mod watch {
+
+    use assert_cmd::cargo::cargo_bin;
+    use std::{
+        fs,
+        io::{BufRead, BufReader},
+        process::{Command, Stdio},
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+    use tempfile::tempdir;
+
+    #[test]
+    fn regenerate_ts_when_module_files_change() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        fs::write(src.join("lib.rs"), "mod types;\n").unwrap();
+        fs::write(src.join("types.rs"), "type A = u32;\n").unwrap();
+        let output = dir.path().join("bindings.ts");
+
+        let mut child = Command::new(cargo_bin("near-syn"))
+            .args(["ts", "--no-now", "--watch", "-o"])
+            .arg(&output)
+            .arg("--crate")
+            .arg(dir.path().join("Cargo.toml"))
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let (tx, rx) = mpsc::channel();
+        let stderr = BufReader::new(child.stderr.take().unwrap());
+        thread::spawn(move || {
+            for line in stderr.lines() {
+                tx.send(line.unwrap()).unwrap();
+            }
+        });
+        let updated = format!("updated `{}`", output.display());
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), updated);
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains("export type A = number;"));
+
+        fs::write(src.join("types.rs"), "type A = String;\n").unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)).unwrap(), updated);
+        let result = fs::read_to_string(&output).unwrap();
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(result.contains("export type A = string;"));
+        assert!(!result.contains("export type A = number;"));
+    }
+}
+
+mod errors {
+
+    use super::near_cmd;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn report_unsupported_types_with_source_location() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "#[derive(Serialize)]\nstruct A {{\n    p: *const u32,\n}}"
+        )
+        .unwrap();
+        let path = file.into_temp_path();
+
+        near_cmd("ts")
+            .arg(path.to_str().unwrap())
+            .assert()
+            .code(1)
+            .stdout("")
+            .stderr(format!(
+                "error: type not supported: `* const u32`\n --> {}:3:8\n  |\n3 |     p: *const u32,\n  |        ^\n",
+                path.display()
+            ));
+
+        path.close().unwrap();
+    }
+
+    #[test]
+    fn warn_on_unresolved_paths_and_keep_generating() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "#[derive(Serialize)]\nstruct A {{\n    x: some_dep::Thing,\n}}"
+        )
+        .unwrap();
+        let path = file.into_temp_path();
+
+        let output = near_cmd("ts").arg(path.to_str().unwrap()).output().unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        assert!(String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("    x: Thing;"));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            format!(
+                "warning: cannot resolve type path `some_dep::Thing`, consider importing it with `use`\n --> {}:3:8\n  |\n3 |     x: some_dep::Thing,\n  |        ^\n",
+                path.display()
+            )
+        );
+
+        path.close().unwrap();
+    }
+
+    #[test]
+    fn report_syntax_errors_with_source_location() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "struct A {{\n    p: u32\n\nfn f() {{}}").unwrap();
+        let path = file.into_temp_path();
+
+        let output = near_cmd("md").arg(path.to_str().unwrap()).output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.starts_with("error: "));
+        assert!(stderr.contains(&format!(" --> {}:", path.display())));
+
+        path.close().unwrap();
+    }
+}
+
+mod config {
+
+    use assert_cmd::Command;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn generate_all_outputs_without_arguments() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "#[near_bindgen]\nstruct Contract {}\n\n#[near_bindgen]\nimpl Contract {\n    pub fn get(&self) -> Timestamp { 0 }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("README.md"),
+            "<!-- AUTOGENERATED near-syn:methods-table:start, DO NOT MODIFY -->\n<!-- AUTOGENERATED near-syn:methods-table:end, DO NOT MODIFY -->\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"[package]
+name = "contract"
+
+[package.metadata.near-syn]
+naming.contract = "Market"
+
+[package.metadata.near-syn.ts]
+output = "web/contract.ts"
+types = { Timestamp = "bigint" }
+
+[package.metadata.near-syn.md]
+readme = "README.md"
+
+[package.metadata.near-syn.abi]
+output = "abi.json"
+"#,
+        )
+        .unwrap();
+
+        let near_syn = || {
+            let mut cmd = Command::cargo_bin("near-syn").unwrap();
+            cmd.current_dir(dir.path());
+            cmd
+        };
+
+        near_syn().assert().code(0).stdout("").stderr("");
+        let ts = fs::read_to_string(dir.path().join("web/contract.ts")).unwrap();
+        assert!(ts.contains("export type Timestamp = bigint;\n"));
+        assert!(ts.contains("export interface Market {"));
+        let readme = fs::read_to_string(dir.path().join("README.md")).unwrap();
+        assert!(readme.contains("`get`"));
+        let abi = fs::read_to_string(dir.path().join("abi.json")).unwrap();
+        assert!(abi.contains("\"name\": \"Market\""));
+
+        near_syn().arg("--check").assert().code(0).stdout("");
+
+        fs::write(dir.path().join("abi.json"), "{}\n").unwrap();
+        near_syn()
+            .arg("--check")
+            .assert()
+            .code(1)
+            .stderr("error: `abi.json` is out of date\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("abi.json")).unwrap(),
+            "{}\n"
+        );
+    }
+
+    #[test]
+    fn read_the_given_configuration_file() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        fs::write(
+            &config,
+            "files = []\n\n[schema]\noutput = \"schema.json\"\n",
+        )
+        .unwrap();
+
+        Command::cargo_bin("near-syn")
+            .unwrap()
+            .arg("--config")
+            .arg(&config)
+            .assert()
+            .code(0);
+        assert!(dir.path().join("schema.json").is_file());
+    }
+
+    #[test]
+    fn reject_missing_or_invalid_configuration() {
+        let dir = tempdir().unwrap();
+        let output = Command::cargo_bin("near-syn")
+            .unwrap()
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr)
+            .starts_with("error: no `near-syn.toml` file nor `[package.metadata.near-syn]` table"));
+
+        fs::write(
+            dir.path().join("near-syn.toml"),
+            "[ts]\noutptu = \"a.ts\"\n",
+        )
+        .unwrap();
+        Command::cargo_bin("near-syn")
+            .unwrap()
+            .current_dir(dir.path())
+            .assert()
+            .code(1)
+            .stderr(concat!(
+                "error: unknown field `outptu`, expected one of `output`, `split`, `factory`, `actions`, `zod`, `sdk`, `prelude-from`, `types`\n",
+                " --> ./near-syn.toml:2:1\n",
+                "  |\n",
+                "2 | outptu = \"a.ts\"\n",
+                "  | ^\n",
+            ));
+    }
 }
@@ -11,7 +11,7 @@ mod ts_items {
         })
         .unwrap();
         let mut contract = Contract::new();
-        contract.push_ast(ast);
+        contract.push_ast(ast).unwrap();
         let mut buf = Vec::new();
         ts_items(&mut buf, &mut contract).unwrap();
         assert_eq!(String::from_utf8_lossy(&buf), "");
@@ -39,7 +39,7 @@ mod ts_items {
 
         let mut buf = Vec::new();
         let mut contract = Contract::new();
-        contract.push_ast(ast);
+        contract.push_ast(ast).unwrap();
         ts_items(&mut buf, &contract).unwrap();
         let out = String::from_utf8(buf).unwrap();
         assert_eq!(
@@ -55,6 +55,40 @@ export interface IContract {
 
 }
 
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_skip_doc_attributes_other_than_comments() {
+        let ast: File = parse2(quote! {
+            #[near_bindgen]
+            impl Contract {
+                /// Returns the total.
+                #[doc(hidden)]
+                pub fn get_total(&self) -> U128 {
+                    self.total
+                }
+            }
+        })
+        .unwrap();
+
+        let mut buf = Vec::new();
+        let mut contract = Contract::new();
+        contract.push_ast(ast).unwrap();
+        ts_items(&mut buf, &contract).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            r#"/**
+ */
+export interface Contract {
+    /**
+     * Returns the total.
+     */
+    get_total(): Promise<U128>;
+
+}
+
 "#
         );
     }
@@ -83,7 +117,7 @@ export interface IContract {
 
         let mut buf = Vec::new();
         let mut contract = Contract::new();
-        contract.push_ast(ast);
+        contract.push_ast(ast).unwrap();
         ts_items(&mut buf, &contract).unwrap();
         let out = String::from_utf8(buf).unwrap();
         assert_eq!(
@@ -144,7 +178,7 @@ export interface IContract {
 
         let mut buf = Vec::new();
         let mut contract = Contract::new();
-        contract.push_ast(ast);
+        contract.push_ast(ast).unwrap();
         ts_items(&mut buf, &contract).unwrap();
         let out = String::from_utf8(buf).unwrap();
         println!("{}", out);
@@ -183,10 +217,54 @@ export interface IContract {
 
 mod ts_impl {
 
-    use near_syn::{contract::Contract, ts::ts_impl};
+    use near_syn::{contract::Contract, ts::ts_impl, Error};
     use quote::quote;
     use syn::parse2;
 
+    #[test]
+    fn it_should_omit_private_callbacks_and_callback_params() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse2(quote! {
+                    #[near_bindgen]
+                    impl Contract {
+                        #[private]
+                        pub fn on_transfer(&mut self, sender_id: AccountId, #[callback_unwrap] amount: U128) {}
+                        pub fn on_resolve(&mut self, sender_id: AccountId, #[callback_unwrap] amount: U128) {}
+                        #[private]
+                        pub fn internal(&mut self) {}
+                    }
+                })
+                .unwrap(),
+            )
+            .unwrap();
+
+        let mut buf = Vec::new();
+        near_syn::ts::ts_items(&mut buf, &contract).unwrap();
+        near_syn::ts::ts_contract_methods(&mut buf, &contract).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&buf),
+            r#"/**
+ */
+export interface Contract {
+    /**
+     */
+    on_resolve(args: { sender_id: AccountId }, options?: CallOptions): Promise<void>;
+
+}
+
+export const ContractMethods = {
+    viewMethods: [
+    ],
+    changeMethods: [
+        "on_resolve",
+    ],
+};
+"#
+        );
+    }
+
     #[test]
     fn it_should_omit_non_bindgen_impls() {
         let item_impl = &parse2(quote! {
@@ -222,8 +300,7 @@ mod ts_impl {
     }
 
     #[test]
-    #[should_panic = "Impl struct name not supported"]
-    fn it_should_panic_() {
+    fn it_should_fail_when_impl_type_is_not_a_name() {
         let item_impl = &parse2(quote! {
             #[near_bindgen]
             impl *const u32 {
@@ -232,7 +309,97 @@ mod ts_impl {
         })
         .unwrap();
         let mut buf = Vec::new();
-        ts_impl(&mut buf, item_impl, &Contract::new()).unwrap();
+        let err = ts_impl(&mut buf, item_impl, &Contract::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Impl struct name not supported");
+        assert!(matches!(err, Error::UnsupportedImpl { .. }));
+    }
+}
+
+mod ts_ext_contracts {
+
+    use near_syn::{contract::Contract, ts::ts_ext_contracts};
+    use quote::quote;
+    use syn::parse2;
+
+    fn ext_contracts(contract: &Contract) -> String {
+        let mut buf = Vec::new();
+        ts_ext_contracts(&mut buf, contract).unwrap();
+        String::from_utf8_lossy(&buf).to_string()
+    }
+
+    #[test]
+    fn it_should_name_methods_object_after_trait_when_unnamed() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse2(quote! {
+                    #[ext_contract]
+                    trait SelfCallbacks {
+                        fn on_transfer(&mut self, amount: U128) -> bool;
+                    }
+
+                    trait NotExternal {
+                        fn f(&self);
+                    }
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(contract.ext_contracts, vec!["SelfCallbacks"]);
+        assert_eq!(
+            ext_contracts(&contract),
+            r#"
+/**
+ */
+export interface SelfCallbacks {
+    /**
+     */
+    on_transfer(args: { amount: U128 }, options?: CallOptions): Promise<boolean>;
+
+}
+
+export const SelfCallbacksMethods = {
+    viewMethods: [
+    ],
+    changeMethods: [
+        "on_transfer",
+    ],
+};
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_only_emit_methods_object_of_implemented_traits() {
+        let mut contract = Contract::new();
+        contract
+            .push_ast(
+                parse2(quote! {
+                    #[ext_contract(ext_nft)]
+                    trait NftCore {
+                        fn nft_token(&self, token_id: String) -> Option<Token>;
+                    }
+
+                    #[near_bindgen]
+                    impl NftCore for Contract {
+                        fn nft_token(&self, token_id: String) -> Option<Token> { None }
+                    }
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            ext_contracts(&contract),
+            r#"
+export const ext_nftMethods = {
+    viewMethods: [
+        "nft_token",
+    ],
+    changeMethods: [
+    ],
+};
+"#
+        );
     }
 }
 
@@ -254,16 +421,142 @@ mod ts_struct {
     }
 
     #[test]
-    #[should_panic(expected = "unit struct no supported")]
-    fn it_should_panic_when_unit_struct_is_provided() {
+    fn it_should_fail_when_unit_struct_is_provided() {
         let mut buf = Vec::new();
         let item_struct = &parse2(quote! {
             #[derive(Serialize)]
             struct A;
         })
         .unwrap();
-        ts_struct(&mut buf, item_struct).unwrap();
-        assert_eq!(String::from_utf8_lossy(&buf), "");
+        let err = ts_struct(&mut buf, item_struct).unwrap_err();
+        assert_eq!(err.to_string(), "unit struct not supported: `A`");
+        assert_eq!(String::from_utf8_lossy(&buf), "/**\n */\n");
+    }
+
+    fn ts_fields(tokens: proc_macro2::TokenStream) -> Vec<String> {
+        let mut buf = Vec::new();
+        ts_struct(&mut buf, &parse2(tokens).unwrap()).unwrap();
+        String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .filter(|line| {
+                line.starts_with("    ") && !line.trim_start().starts_with(&['/', '*'][..])
+            })
+            .map(|line| line.trim().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_should_apply_rename_all_rules() {
+        for (rule, expected) in [
+            ("camelCase", "ownerId: AccountId;"),
+            ("PascalCase", "OwnerId: AccountId;"),
+            ("snake_case", "owner_id: AccountId;"),
+            ("SCREAMING_SNAKE_CASE", "OWNER_ID: AccountId;"),
+            ("kebab-case", "\"owner-id\": AccountId;"),
+            ("SCREAMING-KEBAB-CASE", "\"OWNER-ID\": AccountId;"),
+        ] {
+            assert_eq!(
+                ts_fields(quote! {
+                    #[derive(Serialize)]
+                    #[serde(rename_all = #rule)]
+                    struct A { owner_id: AccountId }
+                }),
+                vec![expected]
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_prefer_field_rename_over_rename_all() {
+        assert_eq!(
+            ts_fields(quote! {
+                #[derive(Serialize)]
+                #[serde(rename_all = "camelCase")]
+                struct A {
+                    #[serde(rename = "id")]
+                    token_id: String,
+                    r#type: u8,
+                }
+            }),
+            vec!["id: string;", "type: number;"]
+        );
+    }
+
+    #[test]
+    fn it_should_make_all_fields_optional_with_container_default() {
+        assert_eq!(
+            ts_fields(quote! {
+                #[derive(Serialize, Deserialize)]
+                #[serde(default)]
+                struct A { a: u8, b: bool }
+            }),
+            vec!["a?: number;", "b?: boolean;"]
+        );
+    }
+}
+
+mod ts_enum_serde {
+    use near_syn::ts::ts_enum;
+    use quote::quote;
+    use syn::parse2;
+
+    fn ts(tokens: proc_macro2::TokenStream) -> String {
+        let mut buf = Vec::new();
+        ts_enum(&mut buf, &parse2(tokens).unwrap()).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn it_should_rename_unit_variants() {
+        assert_eq!(
+            ts(quote! {
+                #[derive(Serialize)]
+                #[serde(rename_all = "snake_case")]
+                enum Status { NotStarted, #[serde(skip)] Hidden, Done }
+            }),
+            r#"/**
+ */
+export enum Status {
+    /**
+     */
+    NotStarted = "not_started",
+
+    /**
+     */
+    Done = "done",
+
+}
+
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_rename_variants_and_their_fields() {
+        assert!(ts(quote! {
+            #[derive(Serialize)]
+            #[serde(rename_all = "lowercase")]
+            enum Action {
+                #[serde(rename_all = "camelCase")]
+                Transfer { receiver_id: AccountId, #[serde(default)] memo: String },
+                #[serde(rename = "burn_all")]
+                BurnAll,
+            }
+        })
+        .contains(
+            "    | { transfer: { receiverId: AccountId; memo?: string } }\n\n    /**\n     */\n    | \"burn_all\";\n"
+        ));
+    }
+
+    #[test]
+    fn it_should_intersect_flattened_fields_of_tagged_variants() {
+        assert!(ts(quote! {
+            #[derive(Serialize)]
+            #[serde(tag = "kind")]
+            enum Event { Mint { amount: U128, #[serde(flatten)] extra: Extra } }
+        })
+        .contains("    | { kind: \"Mint\"; amount: U128 } & Extra;\n"));
     }
 }
 
@@ -272,6 +565,21 @@ mod ts_enum {
     use quote::quote;
     use syn::parse2;
 
+    #[test]
+    fn it_should_emit_type_parameters_of_generic_enums() {
+        let mut buf = Vec::new();
+        ts_enum(
+            &mut buf,
+            &parse2(quote! {
+                #[derive(Serialize)]
+                enum Either<L, R = String> { Left(L), Right(R) }
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(String::from_utf8_lossy(&buf).contains("export type Either<L, R = string> =\n"));
+    }
+
     #[test]
     fn it_should_omit_non_serde_enums() {
         let mut buf = Vec::new();
@@ -284,6 +592,89 @@ mod ts_enum {
         ts_enum(&mut buf, item_enum).unwrap();
         assert_eq!(String::from_utf8_lossy(&buf), "");
     }
+
+    fn ts_union(tokens: proc_macro2::TokenStream) -> String {
+        let mut buf = Vec::new();
+        ts_enum(&mut buf, &parse2(tokens).unwrap()).unwrap();
+        String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with("    | "))
+            .map(|line| line.trim_start_matches("    | "))
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn it_should_translate_adjacently_tagged_enums() {
+        assert_eq!(
+            ts_union(quote! {
+                #[derive(Serialize)]
+                #[serde(tag = "t", content = "c")]
+                enum E { A { x: u8 }, B(String), C(u8, bool), D }
+            }),
+            r#"{ t: "A"; c: { x: number } }
+{ t: "B"; c: string }
+{ t: "C"; c: [number, boolean] }
+{ t: "D" };"#
+        );
+    }
+
+    #[test]
+    fn it_should_translate_untagged_enums() {
+        assert_eq!(
+            ts_union(quote! {
+                #[derive(Serialize)]
+                #[serde(crate = "near_sdk::serde", untagged)]
+                enum E { A { x: u8 }, B(String), D }
+            }),
+            "{ x: number }\nstring\nnull;"
+        );
+    }
+
+    #[test]
+    fn it_should_translate_internally_tagged_newtype_variants_to_intersections() {
+        assert_eq!(
+            ts_union(quote! {
+                #[derive(Serialize)]
+                #[serde(tag = "kind")]
+                enum E { A(Token), B }
+            }),
+            "{ kind: \"A\" } & Token\n{ kind: \"B\" };"
+        );
+    }
+
+    #[test]
+    fn it_should_translate_unit_only_tagged_enums_to_unions() {
+        assert_eq!(
+            ts_union(quote! {
+                #[derive(Serialize)]
+                #[serde(tag = "kind")]
+                enum E { A, B }
+            }),
+            "{ kind: \"A\" }\n{ kind: \"B\" };"
+        );
+    }
+}
+
+mod ts_typedef {
+    use near_syn::ts::ts_typedef;
+    use quote::quote;
+    use syn::parse2;
+
+    #[test]
+    fn it_should_emit_type_parameters_of_generic_type_aliases() {
+        let mut buf = Vec::new();
+        ts_typedef(
+            &mut buf,
+            &parse2(quote! { type Entries<K, V = U128> = Vec<(K, V)>; }).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&buf),
+            "/**\n */\nexport type Entries<K, V = U128> = [K, V][];\n\n"
+        );
+    }
 }
 
 mod ts_type {
@@ -293,56 +684,113 @@ mod ts_type {
 
     #[test]
     fn it_should_convert_rust_primitive_types() {
-        assert_eq!(ts_type(&parse_str("bool").unwrap()), "boolean");
-        assert_eq!(ts_type(&parse_str("i8").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("u8").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("i16").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("u16").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("i32").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("u32").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("u64").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("i64").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("String").unwrap()), "string");
+        assert_eq!(ts_type(&parse_str("bool").unwrap()).unwrap(), "boolean");
+        assert_eq!(ts_type(&parse_str("i8").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("u8").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("i16").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("u16").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("i32").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("u32").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("usize").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("isize").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("f32").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("f64").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("char").unwrap()).unwrap(), "string");
+        assert_eq!(ts_type(&parse_str("String").unwrap()).unwrap(), "string");
+    }
+
+    #[test]
+    fn it_should_flag_rust_raw_64_and_128_bits_integers() {
+        assert_eq!(ts_type(&parse_str("u64").unwrap()).unwrap(), "RawU64");
+        assert_eq!(ts_type(&parse_str("i64").unwrap()).unwrap(), "RawI64");
+        assert_eq!(ts_type(&parse_str("u128").unwrap()).unwrap(), "RawU128");
+        assert_eq!(ts_type(&parse_str("i128").unwrap()).unwrap(), "RawI128");
+    }
+
+    #[test]
+    fn it_should_convert_rust_arrays_and_slices() {
+        assert_eq!(
+            ts_type(&parse_str("[u8; 2]").unwrap()).unwrap(),
+            "[number, number]"
+        );
+        assert_eq!(
+            ts_type(&parse_str("[Option<U64>; LEN]").unwrap()).unwrap(),
+            "(U64|null)[]"
+        );
+        assert_eq!(ts_type(&parse_str("&[U128]").unwrap()).unwrap(), "U128[]");
+        assert_eq!(ts_type(&parse_str("&str").unwrap()).unwrap(), "string");
+    }
+
+    #[test]
+    fn it_should_convert_rust_smart_pointers() {
+        assert_eq!(ts_type(&parse_str("Box<Token>").unwrap()).unwrap(), "Token");
+        assert_eq!(
+            ts_type(&parse_str("Rc<Vec<U64>>").unwrap()).unwrap(),
+            "U64[]"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Arc<Option<U64>>").unwrap()).unwrap(),
+            "U64|null"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Cow<'static, [U64]>").unwrap()).unwrap(),
+            "U64[]"
+        );
+    }
+
+    #[test]
+    fn it_should_convert_serde_json_value_to_unknown() {
+        assert_eq!(
+            ts_type(&parse_str("serde_json::Value").unwrap()).unwrap(),
+            "unknown"
+        );
+        assert_eq!(
+            ts_type(&parse_str("HashMap<String, serde_json::Value>").unwrap()).unwrap(),
+            "Record<string, unknown>"
+        );
     }
 
     #[test]
     fn it_should_convert_rust_unit_type() {
-        assert_eq!(ts_type(&parse_str("()").unwrap()), "void");
+        assert_eq!(ts_type(&parse_str("()").unwrap()).unwrap(), "void");
     }
 
     #[test]
     fn it_should_convert_rust_shared_reference_types() {
-        assert_eq!(ts_type(&parse_str("&String").unwrap()), "string");
-        assert_eq!(ts_type(&parse_str("&bool").unwrap()), "boolean");
-        assert_eq!(ts_type(&parse_str("&u32").unwrap()), "number");
-        assert_eq!(ts_type(&parse_str("&TokenId").unwrap()), "TokenId");
+        assert_eq!(ts_type(&parse_str("&String").unwrap()).unwrap(), "string");
+        assert_eq!(ts_type(&parse_str("&bool").unwrap()).unwrap(), "boolean");
+        assert_eq!(ts_type(&parse_str("&u32").unwrap()).unwrap(), "number");
+        assert_eq!(ts_type(&parse_str("&TokenId").unwrap()).unwrap(), "TokenId");
     }
 
     #[test]
     fn it_should_convert_rust_standard_and_collection_types() {
-        assert_eq!(ts_type(&parse_str("Option<U64>").unwrap()), "U64|null");
         assert_eq!(
-            ts_type(&parse_str("Option<String>").unwrap()),
+            ts_type(&parse_str("Option<U64>").unwrap()).unwrap(),
+            "U64|null"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Option<String>").unwrap()).unwrap(),
             "string|null"
         );
         assert_eq!(
-            ts_type(&parse_str("Vec<ValidAccountId>").unwrap()),
+            ts_type(&parse_str("Vec<ValidAccountId>").unwrap()).unwrap(),
             "ValidAccountId[]"
         );
         assert_eq!(
-            ts_type(&parse_str("HashSet<ValidAccountId>").unwrap()),
+            ts_type(&parse_str("HashSet<ValidAccountId>").unwrap()).unwrap(),
             "ValidAccountId[]"
         );
         assert_eq!(
-            ts_type(&parse_str("BTreeSet<ValidAccountId>").unwrap()),
+            ts_type(&parse_str("BTreeSet<ValidAccountId>").unwrap()).unwrap(),
             "ValidAccountId[]"
         );
         assert_eq!(
-            ts_type(&parse_str("HashMap<AccountId, U128>").unwrap()),
+            ts_type(&parse_str("HashMap<AccountId, U128>").unwrap()).unwrap(),
             "Record<AccountId, U128>"
         );
         assert_eq!(
-            ts_type(&parse_str("BTreeMap<AccountId, U128>").unwrap()),
+            ts_type(&parse_str("BTreeMap<AccountId, U128>").unwrap()).unwrap(),
             "Record<AccountId, U128>"
         );
     }
@@ -350,85 +798,208 @@ mod ts_type {
     #[test]
     fn it_should_convert_rust_nested_types() {
         assert_eq!(
-            ts_type(&parse_str("HashMap<AccountId, Vec<U128>>").unwrap()),
+            ts_type(&parse_str("HashMap<AccountId, Vec<U128>>").unwrap()).unwrap(),
             "Record<AccountId, U128[]>"
         );
         assert_eq!(
-            ts_type(&parse_str("Vec<Option<U128>>").unwrap()),
+            ts_type(&parse_str("Vec<Option<U128>>").unwrap()).unwrap(),
             "(U128|null)[]"
         );
         assert_eq!(
-            ts_type(&parse_str("Option<Vec<U128>>").unwrap()),
+            ts_type(&parse_str("Option<Vec<U128>>").unwrap()).unwrap(),
             "U128[]|null"
         );
         assert_eq!(
-            ts_type(&parse_str("Option<Option<U64>>").unwrap()),
+            ts_type(&parse_str("Option<Option<U64>>").unwrap()).unwrap(),
             "U64|null|null"
         );
-        assert_eq!(ts_type(&parse_str("Vec<Vec<U64>>").unwrap()), "U64[][]");
-        assert_eq!(ts_type(&parse_str("(U64)").unwrap()), "U64");
+        assert_eq!(
+            ts_type(&parse_str("Vec<Vec<U64>>").unwrap()).unwrap(),
+            "U64[][]"
+        );
+        assert_eq!(ts_type(&parse_str("(U64)").unwrap()).unwrap(), "U64");
     }
 
     #[test]
     fn it_should_convert_rust_tuple_types() {
         assert_eq!(
-            ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()),
+            ts_type(&parse_str("(U64, String, Vec<u32>)").unwrap()).unwrap(),
             "[U64, string, number[]]"
         );
     }
 
     #[test]
-    #[ignore = "path resolution not supported"]
+    fn it_should_convert_rust_result_types() {
+        assert_eq!(
+            ts_type(&parse_str("Result<U64, String>").unwrap()).unwrap(),
+            "{ Ok: U64 }|{ Err: string }"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Vec<Result<u8, String>>").unwrap()).unwrap(),
+            "({ Ok: number }|{ Err: string })[]"
+        );
+    }
+
+    #[test]
+    fn it_should_convert_rust_generic_types() {
+        assert_eq!(
+            ts_type(&parse_str("Option<Page<Token>>").unwrap()).unwrap(),
+            "Page<Token>|null"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Vec<Page<Vec<U64>>>").unwrap()).unwrap(),
+            "Page<U64[]>[]"
+        );
+        assert_eq!(
+            ts_type(&parse_str("Pair<Option<u8>, (bool, String)>").unwrap()).unwrap(),
+            "Pair<number|null, [boolean, string]>"
+        );
+    }
+
+    #[test]
+    fn it_should_report_the_location_of_the_offending_type() {
+        let err = ts_type(&syn::parse_str("Vec<(u32, *const u32)>").unwrap()).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(
+            (location.file.as_ref(), location.line, location.column),
+            (None, 1, 11)
+        );
+    }
+
+    #[test]
     fn it_should_convert_rust_path_types() {
-        assert_eq!(ts_type(&parse_str("std::vec::Vec<U64>").unwrap()), "U64[]");
+        assert_eq!(
+            ts_type(&parse_str("std::vec::Vec<U64>").unwrap()).unwrap(),
+            "U64[]"
+        );
+        assert_eq!(
+            ts_type(&parse_str("near_sdk::json_types::U128").unwrap()).unwrap(),
+            "U128"
+        );
+        assert_eq!(
+            ts_type(&parse_str("std::collections::HashMap<AccountId, crate::Token>").unwrap())
+                .unwrap(),
+            "Record<AccountId, Token>"
+        );
+        assert_eq!(
+            ts_type(&parse_str("::std::option::Option<super::Token>").unwrap()).unwrap(),
+            "Token|null"
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Option used with no generic arg")]
-    fn it_should_panic_on_option_with_no_args() {
-        ts_type(&syn::parse_str("Option").unwrap());
+    fn it_should_fall_back_to_the_last_segment_of_unresolved_path_types() {
+        assert_eq!(
+            ts_type(&parse_str("Vec<other::Token>").unwrap()).unwrap(),
+            "Token[]"
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Option expects 1 generic(s) argument(s), found 2")]
-    fn it_should_panic_on_option_with_more_than_one_arg() {
-        ts_type(&syn::parse_str("Option<String, U128>").unwrap());
+    fn it_should_fail_on_option_with_no_args() {
+        let err = ts_type(&syn::parse_str("Option").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Option used with no generic arguments");
+    }
+
+    #[test]
+    fn it_should_fail_on_option_with_more_than_one_arg() {
+        let err = ts_type(&syn::parse_str("Option<String, U128>").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Option expects 1 generic(s) argument(s), found 2"
+        );
     }
 
     #[test]
-    #[should_panic(expected = "No type provided for Option")]
-    fn it_should_panic_on_option_with_no_generic_type_argument() {
-        ts_type(&syn::parse_str("Option<'a>").unwrap());
+    fn it_should_fail_on_option_with_no_generic_type_argument() {
+        let err = ts_type(&syn::parse_str("Option<'a>").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "No type provided for Option");
     }
 
     #[test]
-    #[should_panic(expected = "Vec used with no generic arg")]
-    fn it_should_panic_on_vec_with_no_args() {
-        ts_type(&syn::parse_str("Vec").unwrap());
+    fn it_should_fail_on_vec_with_no_args() {
+        let err = ts_type(&syn::parse_str("Vec").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Vec used with no generic arguments");
     }
 
     #[test]
-    #[should_panic(expected = "Vec expects 1 generic(s) argument(s), found 3")]
-    fn it_should_panic_on_vec_with_more_than_one_arg() {
-        ts_type(&syn::parse_str("Vec<String, U128, u32>").unwrap());
+    fn it_should_fail_on_vec_with_more_than_one_arg() {
+        let err = ts_type(&syn::parse_str("Vec<String, U128, u32>").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Vec expects 1 generic(s) argument(s), found 3"
+        );
+    }
+
+    #[test]
+    fn it_should_fail_on_hashmap_with_no_args() {
+        let err = ts_type(&syn::parse_str("HashMap").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "HashMap used with no generic arguments");
+    }
+
+    #[test]
+    fn it_should_fail_on_hashmap_with_less_than_two_args() {
+        let err = ts_type(&syn::parse_str("HashMap<U64>").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "HashMap expects 2 generic(s) argument(s), found 1"
+        );
+    }
+
+    #[test]
+    fn it_should_fail_with_not_supported_type() {
+        let err = ts_type(&syn::parse_str("*const u32").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "type not supported: `* const u32`");
+    }
+}
+
+mod ts_sig {
+
+    use near_syn::ts::ts_sig;
+    use syn::{parse_str, ImplItemMethod};
+
+    fn sig(method: &str) -> String {
+        ts_sig(&parse_str::<ImplItemMethod>(method).unwrap()).unwrap()
     }
 
     #[test]
-    #[should_panic(expected = "HashMap used with no generic arguments")]
-    fn it_should_panic_on_hashmap_with_no_args() {
-        ts_type(&syn::parse_str("HashMap").unwrap());
+    fn it_should_pass_borsh_args_as_bytes() {
+        assert_eq!(
+            sig("pub fn set(&mut self, #[serializer(borsh)] key: Vec<u8>, #[serializer(borsh)] value: u64) {}"),
+            "set(args: Uint8Array, options?: CallOptions): Promise<void>;"
+        );
+        assert_eq!(
+            sig("#[init] pub fn new(#[serializer(borsh)] owner: AccountId) -> Self {}"),
+            "new: Uint8Array;"
+        );
+        assert_eq!(
+            sig("#[serializer(borsh)] pub fn set(&mut self, key: String) {}"),
+            "set(args: { key: string }, options?: CallOptions): Promise<void>;"
+        );
     }
 
     #[test]
-    #[should_panic(expected = "HashMap expects 2 generic(s) argument(s), found 1")]
-    fn it_should_panic_on_hashmap_with_less_than_two_args() {
-        ts_type(&syn::parse_str("HashMap<U64>").unwrap());
+    fn it_should_exclude_callback_params() {
+        assert_eq!(
+            sig("pub fn on_transfer(&mut self, sender_id: AccountId, #[callback_unwrap] amount: U128, #[callback_result] memo: Result<String, PromiseError>) {}"),
+            "on_transfer(args: { sender_id: AccountId }, options?: CallOptions): Promise<void>;"
+        );
+        assert_eq!(
+            sig("pub fn on_all(&mut self, #[callback_vec] results: Vec<U128>) -> U128 {}"),
+            "on_all(options?: CallOptions): Promise<U128>;"
+        );
     }
 
     #[test]
-    #[should_panic(expected = "type not supported: ")]
-    fn it_should_panic_with_not_supported_type() {
-        ts_type(&syn::parse_str("*const u32").unwrap());
+    fn it_should_return_borsh_results_as_bytes() {
+        assert_eq!(
+            sig("#[result_serializer(borsh)] pub fn get(&self, key: String) -> Option<u64> {}"),
+            "get(args: { key: string }): Promise<Uint8Array>;"
+        );
+        assert_eq!(
+            sig("#[result_serializer(json)] pub fn get(&self) -> u64 {}"),
+            "get(): Promise<RawU64>;"
+        );
     }
 }
 
@@ -439,23 +1010,52 @@ mod ts_ret_type {
 
     #[test]
     fn it_should_treat_default_return_type_as_unit() {
-        assert_eq!(ts_ret_type(&parse_str(" ").unwrap()), "void");
+        assert_eq!(ts_ret_type(&parse_str(" ").unwrap()).unwrap(), "void");
     }
 
     #[test]
     fn it_should_convert_primitive_and_complex_return_types() {
-        assert_eq!(ts_ret_type(&parse_str("-> Vec<Token>").unwrap()), "Token[]");
-        assert_eq!(ts_ret_type(&parse_str("-> u32").unwrap()), "number");
+        assert_eq!(
+            ts_ret_type(&parse_str("-> Vec<Token>").unwrap()).unwrap(),
+            "Token[]"
+        );
+        assert_eq!(
+            ts_ret_type(&parse_str("-> u32").unwrap()).unwrap(),
+            "number"
+        );
+    }
+
+    #[test]
+    fn it_should_convert_results_to_their_ok_type() {
+        assert_eq!(
+            ts_ret_type(&parse_str("-> Result<Vec<Token>, ContractError>").unwrap()).unwrap(),
+            "Token[]"
+        );
+        assert_eq!(
+            ts_ret_type(&parse_str("-> Result<Promise, String>").unwrap()).unwrap(),
+            "void"
+        );
+        assert_eq!(
+            ts_ret_type(&parse_str("-> Result<(), String>").unwrap()).unwrap(),
+            "void"
+        );
     }
 
     #[test]
     fn it_should_convert_promises_to_void() {
-        assert_eq!(ts_ret_type(&parse_str("-> Promise<u32>").unwrap()), "void");
         assert_eq!(
-            ts_ret_type(&parse_str("-> PromiseOrValue<String>").unwrap()),
+            ts_ret_type(&parse_str("-> Promise<u32>").unwrap()).unwrap(),
             "void"
         );
     }
+
+    #[test]
+    fn it_should_convert_promise_or_values_to_their_value_type() {
+        assert_eq!(
+            ts_ret_type(&parse_str("-> PromiseOrValue<String>").unwrap()).unwrap(),
+            "string"
+        );
+    }
 }
 
 mod ts_doc {